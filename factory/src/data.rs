@@ -0,0 +1,17 @@
+use socketfi_shared_types::Platform;
+use soroban_sdk::{contracttype, Address, String};
+
+pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
+pub(crate) const BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub(crate) const LIFETIME_THRESHOLD: u32 = BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Initialized,
+    Controller,
+    WasmHash,
+    Executors,
+    ProfileAccount(Platform, String),
+    OwnerAccount(Address),
+}
@@ -0,0 +1,170 @@
+use socketfi_shared_types::Platform;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+
+use crate::{
+    registry::{
+        owner_salt, profile_salt, read_controller, read_executors, read_is_initialized,
+        read_owner_account, read_profile_account, read_wasm_hash, write_controller,
+        write_executors, write_is_initialized, write_owner_account, write_profile_account,
+        write_wasm_hash,
+    },
+    subaccount::SubAccountClient,
+};
+
+pub trait SubAccountFactoryTrait {
+    fn init(e: Env, controller_id: Address, wasm_hash: BytesN<32>, executors: Vec<Address>);
+    fn set_wasm_hash(e: Env, wasm_hash: BytesN<32>);
+    fn set_executors(e: Env, executors: Vec<Address>);
+    fn create_with_address(e: Env, owner_id: Address) -> Address;
+    fn create_with_profile(
+        e: Env,
+        platform: Platform,
+        profile_id: String,
+        salt: String,
+        salt_iv: String,
+        key_index: String,
+        index_iv: String,
+        passkey: BytesN<65>,
+        max_allowance: i128,
+    ) -> Address;
+    fn get_profile_account(e: Env, platform: Platform, profile_id: String) -> Option<Address>;
+    fn get_owner_account(e: Env, owner_id: Address) -> Option<Address>;
+    fn get_profile_address(e: Env, platform: Platform, profile_id: String) -> Address;
+    fn get_owner_address(e: Env, owner_id: Address) -> Address;
+    fn get_controller(e: Env) -> Address;
+    fn get_wasm_hash(e: Env) -> BytesN<32>;
+    fn get_executors(e: Env) -> Vec<Address>;
+}
+
+#[contract]
+pub struct SubAccountFactory;
+
+//Deploys the sub-account and runs its initialization and executor setup in the same invocation
+//so no one can initialize a freshly deployed account before the factory does
+fn deploy_subaccount(e: &Env, salt: BytesN<32>) -> (Address, SubAccountClient<'_>) {
+    let wasm_hash = read_wasm_hash(e);
+    let account_id = e.deployer().with_current_contract(salt).deploy(wasm_hash);
+    let client = SubAccountClient::new(e, &account_id);
+    (account_id, client)
+}
+
+fn set_subaccount_executors(e: &Env, client: &SubAccountClient) {
+    let executors = read_executors(e);
+    for (index, executor) in executors.iter().enumerate() {
+        client.set_executor(&(index as u32 + 1), &executor);
+    }
+    client.set_executor_done();
+}
+
+#[contractimpl]
+impl SubAccountFactoryTrait for SubAccountFactory {
+    //The controller signs the initialization so no one else can claim a freshly deployed factory
+    fn init(e: Env, controller_id: Address, wasm_hash: BytesN<32>, executors: Vec<Address>) {
+        if read_is_initialized(&e) {
+            panic!("has already been initilized")
+        }
+        controller_id.require_auth();
+        write_controller(&e, &controller_id);
+        write_wasm_hash(&e, &wasm_hash);
+        write_executors(&e, &executors);
+        write_is_initialized(&e)
+    }
+
+    //Updates the sub-account code used for new deployments only
+    fn set_wasm_hash(e: Env, wasm_hash: BytesN<32>) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        write_wasm_hash(&e, &wasm_hash);
+    }
+
+    fn set_executors(e: Env, executors: Vec<Address>) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        write_executors(&e, &executors);
+    }
+
+    //Creates a sub-account owned by an external wallet, the address is derived from the owner
+    fn create_with_address(e: Env, owner_id: Address) -> Address {
+        owner_id.require_auth();
+        if read_owner_account(&e, owner_id.clone()).is_some() {
+            panic!("Owner already has a sub-account")
+        }
+        let salt = owner_salt(&e, owner_id.clone());
+        let (account_id, client) = deploy_subaccount(&e, salt);
+
+        client.init_with_address(&read_controller(&e), &owner_id);
+        set_subaccount_executors(&e, &client);
+
+        write_owner_account(&e, owner_id, &account_id);
+        account_id
+    }
+
+    //Creates a sub-account for a social profile, the address is derived from platform and profile id
+    //Only the controller can create these since the profile cannot sign
+    fn create_with_profile(
+        e: Env,
+        platform: Platform,
+        profile_id: String,
+        salt: String,
+        salt_iv: String,
+        key_index: String,
+        index_iv: String,
+        passkey: BytesN<65>,
+        max_allowance: i128,
+    ) -> Address {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        if read_profile_account(&e, platform, profile_id.clone()).is_some() {
+            panic!("Profile already has a sub-account")
+        }
+        let deploy_salt = profile_salt(&e, platform, profile_id.clone());
+        let (account_id, client) = deploy_subaccount(&e, deploy_salt);
+
+        client.init_with_profile(
+            &controller,
+            &platform,
+            &profile_id,
+            &salt,
+            &salt_iv,
+            &key_index,
+            &index_iv,
+            &passkey,
+            &max_allowance,
+        );
+        set_subaccount_executors(&e, &client);
+
+        write_profile_account(&e, platform, profile_id, &account_id);
+        account_id
+    }
+
+    fn get_profile_account(e: Env, platform: Platform, profile_id: String) -> Option<Address> {
+        read_profile_account(&e, platform, profile_id)
+    }
+
+    fn get_owner_account(e: Env, owner_id: Address) -> Option<Address> {
+        read_owner_account(&e, owner_id)
+    }
+
+    //Precomputes the address a profile sub-account is (or will be) deployed at
+    fn get_profile_address(e: Env, platform: Platform, profile_id: String) -> Address {
+        let salt = profile_salt(&e, platform, profile_id);
+        e.deployer().with_current_contract(salt).deployed_address()
+    }
+
+    fn get_owner_address(e: Env, owner_id: Address) -> Address {
+        let salt = owner_salt(&e, owner_id);
+        e.deployer().with_current_contract(salt).deployed_address()
+    }
+
+    fn get_controller(e: Env) -> Address {
+        read_controller(&e)
+    }
+
+    fn get_wasm_hash(e: Env) -> BytesN<32> {
+        read_wasm_hash(&e)
+    }
+
+    fn get_executors(e: Env) -> Vec<Address> {
+        read_executors(&e)
+    }
+}
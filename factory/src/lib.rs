@@ -0,0 +1,11 @@
+#![no_std]
+mod data;
+//create_with_profile and its generated client take the full encrypted profile
+#[allow(clippy::too_many_arguments)]
+mod factory;
+mod registry;
+#[allow(clippy::too_many_arguments)]
+mod subaccount;
+mod test;
+
+pub use crate::factory::{SubAccountFactory, SubAccountFactoryClient, SubAccountFactoryTrait};
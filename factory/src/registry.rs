@@ -0,0 +1,101 @@
+use socketfi_shared_types::Platform;
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec};
+
+use crate::data::{DataKey, BUMP_AMOUNT, LIFETIME_THRESHOLD};
+
+//Salt prefixes keep profile and owner derived addresses from colliding
+const PROFILE_SALT_PREFIX: u8 = 0;
+const OWNER_SALT_PREFIX: u8 = 1;
+
+pub fn read_is_initialized(e: &Env) -> bool {
+    let key = DataKey::Initialized;
+    e.storage().instance().get(&key).unwrap_or(false)
+}
+
+pub fn write_is_initialized(e: &Env) {
+    let key = DataKey::Initialized;
+    e.storage().instance().set(&key, &true);
+}
+
+pub fn read_controller(e: &Env) -> Address {
+    let key = DataKey::Controller;
+    e.storage().instance().get(&key).unwrap()
+}
+
+pub fn write_controller(e: &Env, controller_id: &Address) {
+    let key = DataKey::Controller;
+    e.storage().instance().set(&key, controller_id);
+}
+
+pub fn read_wasm_hash(e: &Env) -> BytesN<32> {
+    let key = DataKey::WasmHash;
+    e.storage().instance().get(&key).unwrap()
+}
+
+pub fn write_wasm_hash(e: &Env, wasm_hash: &BytesN<32>) {
+    let key = DataKey::WasmHash;
+    e.storage().instance().set(&key, wasm_hash);
+}
+
+pub fn read_executors(e: &Env) -> Vec<Address> {
+    let key = DataKey::Executors;
+    e.storage().instance().get(&key).unwrap_or(Vec::new(e))
+}
+
+pub fn write_executors(e: &Env, executors: &Vec<Address>) {
+    let key = DataKey::Executors;
+    e.storage().instance().set(&key, executors);
+}
+
+pub fn read_profile_account(e: &Env, platform: Platform, profile_id: String) -> Option<Address> {
+    let key = DataKey::ProfileAccount(platform, profile_id);
+    let account = e.storage().persistent().get::<DataKey, Address>(&key);
+    if account.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+    }
+    account
+}
+
+pub fn write_profile_account(e: &Env, platform: Platform, profile_id: String, account: &Address) {
+    let key = DataKey::ProfileAccount(platform, profile_id);
+    e.storage().persistent().set(&key, account);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn read_owner_account(e: &Env, owner_id: Address) -> Option<Address> {
+    let key = DataKey::OwnerAccount(owner_id);
+    let account = e.storage().persistent().get::<DataKey, Address>(&key);
+    if account.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+    }
+    account
+}
+
+pub fn write_owner_account(e: &Env, owner_id: Address, account: &Address) {
+    let key = DataKey::OwnerAccount(owner_id);
+    e.storage().persistent().set(&key, account);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn profile_salt(e: &Env, platform: Platform, profile_id: String) -> BytesN<32> {
+    let mut salt = Bytes::new(e);
+    salt.push_back(PROFILE_SALT_PREFIX);
+    salt.append(&platform.to_xdr(e));
+    salt.append(&profile_id.to_xdr(e));
+    e.crypto().sha256(&salt).into()
+}
+
+pub fn owner_salt(e: &Env, owner_id: Address) -> BytesN<32> {
+    let mut salt = Bytes::new(e);
+    salt.push_back(OWNER_SALT_PREFIX);
+    salt.append(&owner_id.to_xdr(e));
+    e.crypto().sha256(&salt).into()
+}
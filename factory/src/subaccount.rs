@@ -0,0 +1,23 @@
+use socketfi_shared_types::Platform;
+use soroban_sdk::{contractclient, Address, BytesN, Env, String};
+
+//Entry points of the sub-account used by the factory during creation
+#[allow(dead_code)]
+#[contractclient(name = "SubAccountClient")]
+pub trait SubAccountInterface {
+    fn init_with_address(e: Env, controller_id: Address, owner_id: Address);
+    fn init_with_profile(
+        e: Env,
+        controller_id: Address,
+        platform: Platform,
+        profile_id: String,
+        salt: String,
+        salt_iv: String,
+        key_index: String,
+        index_iv: String,
+        passkey: BytesN<65>,
+        max_allowance: i128,
+    );
+    fn set_executor(e: Env, index: u32, executor: Address);
+    fn set_executor_done(e: Env);
+}
@@ -0,0 +1,157 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::factory::{SubAccountFactory, SubAccountFactoryClient};
+use socketfi_shared_types::Platform;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
+
+//Release build of the sub-account committed under wasm/
+#[allow(clippy::too_many_arguments)]
+mod subaccount_wasm {
+    soroban_sdk::contractimport!(file = "../wasm/socketfi_smart_subaccount.wasm");
+}
+
+//Deploying from wasm costs far more than the default test budget allows
+fn upload_subaccount_wasm(env: &Env) -> BytesN<32> {
+    env.budget().reset_unlimited();
+    env.deployer().upload_contract_wasm(subaccount_wasm::WASM)
+}
+
+fn create_factory<'a>(env: &Env, wasm_hash: &BytesN<32>) -> (SubAccountFactoryClient<'a>, Address) {
+    let controller = Address::generate(env);
+    let executors = vec![env, Address::generate(env), Address::generate(env)];
+    let factory_id = env.register_contract(None, SubAccountFactory);
+    let factory = SubAccountFactoryClient::new(env, &factory_id);
+    factory
+        .mock_all_auths()
+        .init(&controller, wasm_hash, &executors);
+    (factory, controller)
+}
+
+#[test]
+#[should_panic(expected = "has already been initilized")]
+fn test_init_twice() {
+    let env = Env::default();
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    let (factory, controller) = create_factory(&env, &wasm_hash);
+    factory.init(&controller, &wasm_hash, &vec![&env]);
+}
+
+#[test]
+#[should_panic]
+fn test_init_requires_controller() {
+    let env = Env::default();
+    let factory_id = env.register_contract(None, SubAccountFactory);
+    let factory = SubAccountFactoryClient::new(&env, &factory_id);
+    factory.init(
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[1; 32]),
+        &vec![&env],
+    );
+}
+
+#[test]
+fn test_deterministic_addresses() {
+    let env = Env::default();
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    let (factory, _) = create_factory(&env, &wasm_hash);
+    let owner = Address::generate(&env);
+    let platform = Platform::X;
+    let profile_id = String::from_str(&env, "1234567890");
+
+    assert_eq!(
+        factory.get_owner_address(&owner),
+        factory.get_owner_address(&owner)
+    );
+    assert_ne!(
+        factory.get_owner_address(&owner),
+        factory.get_owner_address(&Address::generate(&env))
+    );
+    assert_ne!(
+        factory.get_profile_address(&platform, &profile_id),
+        factory.get_profile_address(&Platform::Telegram, &profile_id)
+    );
+    assert_eq!(factory.get_owner_account(&owner), None);
+    assert_eq!(factory.get_profile_account(&platform, &profile_id), None);
+}
+
+#[test]
+#[should_panic]
+fn test_set_wasm_hash_requires_controller() {
+    let env = Env::default();
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    let (factory, _) = create_factory(&env, &wasm_hash);
+    factory.set_wasm_hash(&BytesN::from_array(&env, &[2; 32]));
+}
+
+#[test]
+fn test_create_with_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let wasm_hash = upload_subaccount_wasm(&env);
+    let (factory, _) = create_factory(&env, &wasm_hash);
+    let owner = Address::generate(&env);
+
+    let expected = factory.get_owner_address(&owner);
+    let account_id = factory.create_with_address(&owner);
+    assert_eq!(account_id, expected);
+    assert_eq!(factory.get_owner_account(&owner), Some(account_id.clone()));
+
+    let account = subaccount_wasm::Client::new(&env, &account_id);
+    assert_eq!(account.get_signer_weight(&owner), 1);
+    let executors = factory.get_executors();
+    assert_eq!(account.get_executor(&1), executors.get(0).unwrap());
+    assert_eq!(account.get_executor(&2), executors.get(1).unwrap());
+
+    //initialization and executors are already locked once the factory returns
+    assert!(account
+        .try_init_with_address(&Address::generate(&env), &Address::generate(&env))
+        .is_err());
+    assert!(account
+        .try_set_executor(&3, &Address::generate(&env))
+        .is_err());
+}
+
+#[test]
+fn test_create_with_profile() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let wasm_hash = upload_subaccount_wasm(&env);
+    let (factory, _) = create_factory(&env, &wasm_hash);
+    let platform = Platform::X;
+    let profile_id = String::from_str(&env, "1234567890");
+    let value = String::from_str(&env, "encrypted");
+
+    let expected = factory.get_profile_address(&platform, &profile_id);
+    let account_id = factory.create_with_profile(
+        &platform,
+        &profile_id,
+        &value,
+        &value,
+        &value,
+        &value,
+        &BytesN::from_array(&env, &[4; 65]),
+        &1000,
+    );
+    assert_eq!(account_id, expected);
+    assert_eq!(
+        factory.get_profile_account(&platform, &profile_id),
+        Some(account_id.clone())
+    );
+
+    let account = subaccount_wasm::Client::new(&env, &account_id);
+    assert_eq!(account.get_allowance(), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Owner already has a sub-account")]
+fn test_create_with_address_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let wasm_hash = upload_subaccount_wasm(&env);
+    let (factory, _) = create_factory(&env, &wasm_hash);
+    let owner = Address::generate(&env);
+
+    factory.create_with_address(&owner);
+    factory.create_with_address(&owner);
+}
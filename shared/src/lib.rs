@@ -0,0 +1,7 @@
+#![no_std]
+mod platform;
+mod test;
+mod user_points;
+
+pub use platform::Platform;
+pub use user_points::UserPoints;
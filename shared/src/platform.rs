@@ -0,0 +1,12 @@
+use soroban_sdk::contracttype;
+
+//Platform used to create or link a profile, the values are shared with the controller
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Platform {
+    Stellar = 0,
+    Email = 1,
+    X = 2,
+    Discord = 3,
+    Telegram = 4,
+}
@@ -0,0 +1,84 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::{Platform, UserPoints};
+use soroban_sdk::{
+    xdr::{Limits, ScMap, ScMapEntry, ScSymbol, ScVal, WriteXdr},
+    Bytes, Env, IntoVal, TryFromVal, Val,
+};
+
+fn entry(key: &str, val: ScVal) -> ScMapEntry {
+    ScMapEntry {
+        key: ScVal::Symbol(ScSymbol(key.try_into().unwrap())),
+        val,
+    }
+}
+
+//The encoding the controller expects: a map keyed by field name, sorted, with points as u32
+fn expected_xdr(user_points: &UserPoints) -> std::vec::Vec<u8> {
+    let map = ScMap(
+        std::vec![
+            entry("has_received", ScVal::Bool(user_points.has_received)),
+            entry("has_sent", ScVal::Bool(user_points.has_sent)),
+            entry(
+                "has_set_allowance",
+                ScVal::Bool(user_points.has_set_allowance)
+            ),
+            entry("has_set_signer", ScVal::Bool(user_points.has_set_signer)),
+            entry("points", ScVal::U32(user_points.points)),
+        ]
+        .try_into()
+        .unwrap(),
+    );
+    ScVal::Map(Some(map)).to_xdr(Limits::none()).unwrap()
+}
+
+#[test]
+fn test_user_points_xdr_encoding() {
+    let env = Env::default();
+    let user_points = UserPoints {
+        has_received: true,
+        has_sent: false,
+        has_set_allowance: true,
+        has_set_signer: false,
+        points: 2750,
+    };
+
+    let encoded = soroban_sdk::xdr::ToXdr::to_xdr(user_points.clone(), &env);
+    let expected = expected_xdr(&user_points);
+    assert_eq!(encoded, Bytes::from_slice(&env, &expected));
+}
+
+#[test]
+fn test_user_points_roundtrip() {
+    let env = Env::default();
+    let user_points = UserPoints {
+        has_received: false,
+        has_sent: true,
+        has_set_allowance: false,
+        has_set_signer: true,
+        points: 2000,
+    };
+
+    let val: Val = user_points.clone().into_val(&env);
+    let decoded = UserPoints::try_from_val(&env, &val).unwrap();
+    assert_eq!(decoded, user_points);
+}
+
+#[test]
+fn test_platform_xdr_encoding() {
+    let env = Env::default();
+    let platforms = [
+        (Platform::Stellar, 0),
+        (Platform::Email, 1),
+        (Platform::X, 2),
+        (Platform::Discord, 3),
+        (Platform::Telegram, 4),
+    ];
+
+    for (platform, value) in platforms {
+        let encoded = soroban_sdk::xdr::ToXdr::to_xdr(platform, &env);
+        let expected = ScVal::U32(value).to_xdr(Limits::none()).unwrap();
+        assert_eq!(encoded, Bytes::from_slice(&env, &expected));
+    }
+}
@@ -0,0 +1,13 @@
+use soroban_sdk::contracttype;
+
+//Quest data shared with the controller, the field names and types are part of the
+//XDR encoding the controller reads and must not change
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UserPoints {
+    pub has_received: bool,
+    pub has_sent: bool,
+    pub has_set_allowance: bool,
+    pub has_set_signer: bool,
+    pub points: u32,
+}
@@ -1,14 +1,21 @@
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
 
-use crate::data::{DataKey, EncryptedKeys, ProfileEncrypted, BUMP_AMOUNT, LIFETIME_THRESHOLD};
+use crate::{
+    data::{
+        DataKey, EncryptedKeys, LinkedProfile, PendingOwner, ProfileEncrypted, BUMP_AMOUNT,
+        LIFETIME_THRESHOLD, MAX_LINKED_PROFILES,
+    },
+    fees::reimburse_fee,
+    lockout::record_pkey_attempt,
+    platform::validate_profile_id,
+    signers::has_signers,
+    transact::{increase_nonce, read_nonce},
+    types::Platform,
+};
 //Smart wallet sub account owner
 pub fn read_is_initialized(e: &Env) -> bool {
     let key = DataKey::Initialized;
-    if let Some(flag) = e.storage().instance().get(&key) {
-        flag
-    } else {
-        false
-    }
+    e.storage().instance().get(&key).unwrap_or(false)
 }
 
 pub fn write_is_initialized(e: &Env) {
@@ -18,11 +25,7 @@ pub fn write_is_initialized(e: &Env) {
 
 pub fn read_executors_set(e: &Env) -> bool {
     let key = DataKey::ExecutorsSet;
-    if let Some(flag) = e.storage().instance().get(&key) {
-        flag
-    } else {
-        false
-    }
+    e.storage().instance().get(&key).unwrap_or(false)
 }
 
 pub fn write_executors_set(e: &Env) {
@@ -30,18 +33,6 @@ pub fn write_executors_set(e: &Env) {
     e.storage().instance().set(&key, &true);
 }
 
-pub fn read_excecutor_count(e: &Env) -> u32 {
-    let key = DataKey::ExcecutorCount;
-    if let Some(count) = e.storage().persistent().get::<DataKey, u32>(&key) {
-        e.storage()
-            .persistent()
-            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
-        count
-    } else {
-        0
-    }
-}
-
 pub fn write_excecutor_count(e: &Env, new_count: u32) {
     let key = DataKey::ExcecutorCount;
     e.storage().persistent().set(&key, &new_count);
@@ -65,86 +56,281 @@ pub fn read_executor(e: &Env, index: u32) -> Address {
 
 pub fn read_is_executor(e: &Env, caller_id: Address) -> bool {
     let key = DataKey::IsExecutor(caller_id);
-    if let Some(flag) = e.storage().instance().get(&key) {
-        flag
-    } else {
-        false
-    }
+    e.storage().instance().get(&key).unwrap_or(false)
 }
 
-pub fn has_owner(e: &Env) -> bool {
-    let key = DataKey::Owner;
-    e.storage().instance().has(&key)
+pub fn read_pending_owner(e: &Env) -> Option<PendingOwner> {
+    let key = DataKey::PendingOwner;
+    e.storage().instance().get(&key)
 }
 
-pub fn read_owner(e: &Env) -> Option<Address> {
-    let key = DataKey::Owner;
-    e.storage().instance().get(&key).unwrap()
+pub fn write_pending_owner(e: &Env, pending: &PendingOwner) {
+    let key = DataKey::PendingOwner;
+    e.storage().instance().set(&key, pending);
 }
 
-pub fn write_owner(e: &Env, owner_id: &Address) {
-    let key = DataKey::Owner;
-    e.storage().instance().set(&key, owner_id);
+pub fn remove_pending_owner(e: &Env) {
+    let key = DataKey::PendingOwner;
+    e.storage().instance().remove(&key);
 }
 
-pub fn read_profile_encrypted(e: &Env) -> ProfileEncrypted {
-    let key = DataKey::ProfileKeys;
-    e.storage().instance().get(&key).unwrap()
+pub fn read_linked_profiles(e: &Env) -> Vec<LinkedProfile> {
+    let key = DataKey::LinkedProfiles;
+    e.storage().instance().get(&key).unwrap_or(Vec::new(e))
 }
 
-pub fn write_profile_encrypted(
-    e: &Env,
-    platform: String,
-    profile_id: String,
-    salt: String,
-    salt_iv: String,
-    key_index: String,
-    index_iv: String,
-) {
-    let key = DataKey::ProfileKeys;
-    let user_profile_encrypted = ProfileEncrypted {
-        platform: platform,
-        profile_id: profile_id,
-        gen_salt_encrypted: salt,
-        salt_encryption_iv: salt_iv,
-        index_encrypted: key_index,
-        index_encryption_iv: index_iv,
-    };
-    e.storage().instance().set(&key, &user_profile_encrypted);
+fn write_linked_profiles(e: &Env, profiles: &Vec<LinkedProfile>) {
+    let key = DataKey::LinkedProfiles;
+    e.storage().instance().set(&key, profiles);
+}
+
+pub fn has_profile(e: &Env, platform: Platform, profile_id: String) -> bool {
+    let key = DataKey::ProfileKeys(platform, profile_id);
+    e.storage().instance().has(&key)
+}
+
+pub fn read_profile_encrypted(e: &Env, platform: Platform, profile_id: String) -> ProfileEncrypted {
+    let key = DataKey::ProfileKeys(platform, profile_id);
+    e.storage()
+        .instance()
+        .get(&key)
+        .expect("Profile is not linked to this account")
 }
 
-pub fn write_passkey_hash(e: &Env, passkey_hash: String) {
-    let key = DataKey::PassKeyHash;
-    e.storage().instance().set(&key, &passkey_hash);
+pub fn write_profile_encrypted(e: &Env, profile: ProfileEncrypted, passkey: BytesN<65>) {
+    let platform = profile.platform;
+    let profile_id = profile.profile_id.clone();
+    if !validate_profile_id(platform, &profile_id) {
+        panic!("Invalid profile id for platform")
+    }
+    if has_profile(e, platform, profile_id.clone()) {
+        panic!("Profile is already linked")
+    }
+    let mut profiles = read_linked_profiles(e);
+    if profiles.len() >= MAX_LINKED_PROFILES {
+        panic!("Maximum number of linked profiles reached")
+    }
+    profiles.push_back(LinkedProfile {
+        platform,
+        profile_id: profile_id.clone(),
+    });
+    write_linked_profiles(e, &profiles);
+
+    let key = DataKey::ProfileKeys(platform, profile_id.clone());
+    e.storage().instance().set(&key, &profile);
+    write_passkey(e, platform, profile_id, passkey);
+}
+
+//Unlinks a profile, the last credential can only be removed when the account has signers
+pub fn remove_profile_encrypted(e: &Env, platform: Platform, profile_id: String) {
+    if !has_profile(e, platform, profile_id.clone()) {
+        panic!("Profile is not linked to this account")
+    }
+    let mut profiles = read_linked_profiles(e);
+    if profiles.len() == 1 && !has_signers(e) {
+        panic!("Cannot remove the last credential of the account")
+    }
+    let linked = LinkedProfile {
+        platform,
+        profile_id: profile_id.clone(),
+    };
+    if let Some(index) = profiles.first_index_of(&linked) {
+        profiles.remove(index);
+    }
+    write_linked_profiles(e, &profiles);
+
+    e.storage()
+        .instance()
+        .remove(&DataKey::ProfileKeys(platform, profile_id.clone()));
+    e.storage()
+        .instance()
+        .remove(&DataKey::PassKey(platform, profile_id));
 }
 
-pub fn read_encrypted_keys(e: &Env, profile_id: String) -> EncryptedKeys {
-    let profile_encrypted = read_profile_encrypted(e);
-    if profile_id != profile_encrypted.profile_id {
-        panic!("Profile id is not a match")
+//Replaces the passkey and encrypted key material of a linked profile
+pub fn rotate_profile_encrypted(e: &Env, profile: ProfileEncrypted, passkey: BytesN<65>) {
+    let platform = profile.platform;
+    let profile_id = profile.profile_id.clone();
+    if !has_profile(e, platform, profile_id.clone()) {
+        panic!("Profile is not linked to this account")
     }
-    let encrypted_keys = EncryptedKeys {
+    let key = DataKey::ProfileKeys(platform, profile_id.clone());
+    e.storage().instance().set(&key, &profile);
+    write_passkey(e, platform, profile_id, passkey);
+}
+
+//Uncompressed secp256k1 public key of the profile passkey, proofs are signatures by this key
+pub fn write_passkey(e: &Env, platform: Platform, profile_id: String, passkey: BytesN<65>) {
+    let key = DataKey::PassKey(platform, profile_id);
+    e.storage().instance().set(&key, &passkey);
+}
+
+pub fn read_encrypted_keys(e: &Env, platform: Platform, profile_id: String) -> EncryptedKeys {
+    let profile_encrypted = read_profile_encrypted(e, platform, profile_id);
+    EncryptedKeys {
         gen_salt_encrypted: profile_encrypted.gen_salt_encrypted,
         salt_encryption_iv: profile_encrypted.salt_encryption_iv,
         index_encrypted: profile_encrypted.index_encrypted,
         index_encryption_iv: profile_encrypted.index_encryption_iv,
+    }
+}
+
+//Credential epoch, bumped on every credential rotation
+pub fn read_credential_epoch(e: &Env) -> u32 {
+    let key = DataKey::CredentialEpoch;
+    e.storage().instance().get(&key).unwrap_or(0)
+}
+
+pub fn increase_credential_epoch(e: &Env) -> u32 {
+    let key = DataKey::CredentialEpoch;
+    let new_epoch = read_credential_epoch(e) + 1;
+    e.storage().instance().set(&key, &new_epoch);
+    new_epoch
+}
+
+//Payload the passkey signs, binding the account, the credential nonce, the expiration ledger
+//and the invoked action with its arguments
+pub fn pkey_payload(e: &Env, nonce: u32, expiration_ledger: u32, action: &Bytes) -> Bytes {
+    let mut payload = Bytes::new(e);
+    payload.append(&e.current_contract_address().to_xdr(e));
+    payload.append(&nonce.to_xdr(e));
+    payload.append(&expiration_ledger.to_xdr(e));
+    payload.append(action);
+    payload
+}
+
+//The proof is a recoverable secp256k1 signature over the sha256 of the payload, the 64 byte
+//signature followed by the recovery id. Returns the public key that signed it
+fn recover_passkey(e: &Env, payload: &Bytes, passkey_proof: &BytesN<65>) -> Option<BytesN<65>> {
+    let proof = passkey_proof.to_array();
+    let recovery_id = proof[64] as u32;
+    if recovery_id > 3 {
+        return None;
+    }
+    let mut signature = [0u8; 64];
+    signature.copy_from_slice(&proof[..64]);
+    let digest = e.crypto().sha256(payload);
+    let signature = BytesN::from_array(e, &signature);
+    Some(
+        e.crypto()
+            .secp256k1_recover(&digest, &signature, recovery_id),
+    )
+}
+
+pub fn pkey_action(e: &Env, function: &str, args: impl IntoVal<Env, Val>) -> Bytes {
+    let args: Val = args.into_val(e);
+    (Symbol::new(e, function), args).to_xdr(e)
+}
+
+//Checks the signer of the proof against one linked profile, on success the profile nonce is
+//consumed
+fn check_profile_proof(
+    e: &Env,
+    profile: &LinkedProfile,
+    nonce: u32,
+    signer: &Option<BytesN<65>>,
+) -> bool {
+    let key = DataKey::PassKey(profile.platform, profile.profile_id.clone());
+    let Some(passkey) = e.storage().instance().get::<DataKey, BytesN<65>>(&key) else {
+        return false;
     };
-    encrypted_keys
+    if signer.as_ref() != Some(&passkey) {
+        return false;
+    }
+    if nonce != read_nonce(e, profile.platform, profile.profile_id.clone()) {
+        panic!("Invalid nonce")
+    }
+    increase_nonce(e, profile.platform, profile.profile_id.clone());
+    true
 }
 
-pub fn check_zk_validation(e: &Env, entered_passkey_hash: String) -> bool {
-    let key = DataKey::PassKeyHash;
-    let stored_passkey_hash = e.storage().instance().get(&key).unwrap();
-    entered_passkey_hash == stored_passkey_hash
+fn check_expiration(e: &Env, expiration_ledger: u32) {
+    if e.ledger().sequence() > expiration_ledger {
+        panic!("Passkey authorization has expired")
+    }
+}
+
+//Passkey authorization of an entry point, submitted by the executor at executor_index
+pub struct PkeyAuth {
+    pub executor_index: u32,
+    pub nonce: u32,
+    pub expiration_ledger: u32,
+    pub passkey_proof: BytesN<65>,
+}
+
+impl PkeyAuth {
+    pub fn new(
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+    ) -> Self {
+        PkeyAuth {
+            executor_index,
+            nonce,
+            expiration_ledger,
+            passkey_proof,
+        }
+    }
+}
+
+//Runs the action of a pkey entry point. The executor must sign, a rejected proof is recorded
+//against the lockout and returns None, otherwise the action runs and the executor is reimbursed
+pub fn run_pkey<T>(
+    e: &Env,
+    auth: PkeyAuth,
+    function: &str,
+    args: impl IntoVal<Env, Val>,
+    action: impl FnOnce() -> T,
+) -> Option<T> {
+    run_pkey_for(e, auth, None, function, args, action)
+}
+
+//Same as run_pkey but only a proof from the given profile is accepted
+pub fn run_profile_pkey<T>(
+    e: &Env,
+    auth: PkeyAuth,
+    profile: LinkedProfile,
+    function: &str,
+    args: impl IntoVal<Env, Val>,
+    action: impl FnOnce() -> T,
+) -> Option<T> {
+    run_pkey_for(e, auth, Some(profile), function, args, action)
+}
+
+fn run_pkey_for<T>(
+    e: &Env,
+    auth: PkeyAuth,
+    profile: Option<LinkedProfile>,
+    function: &str,
+    args: impl IntoVal<Env, Val>,
+    action: impl FnOnce() -> T,
+) -> Option<T> {
+    let executor = read_executor(e, auth.executor_index);
+    executor.require_auth();
+    check_expiration(e, auth.expiration_ledger);
+    let invocation = pkey_action(e, function, args);
+    let payload = pkey_payload(e, auth.nonce, auth.expiration_ledger, &invocation);
+    let signer = recover_passkey(e, &payload, &auth.passkey_proof);
+    //a profile bound action only accepts that profile, any linked profile otherwise
+    let authorized = match profile {
+        Some(profile) => check_profile_proof(e, &profile, auth.nonce, &signer),
+        None => read_linked_profiles(e)
+            .iter()
+            .any(|profile| check_profile_proof(e, &profile, auth.nonce, &signer)),
+    };
+    if !record_pkey_attempt(e, &executor, authorized) {
+        return None;
+    }
+
+    let result = action();
+    reimburse_fee(e, &executor);
+    Some(result)
 }
 
 pub fn read_max_allowance(e: &Env) -> i128 {
     let key = DataKey::MaxAllowance;
-    if let Some(allowance) = e.storage().instance().get(&key).unwrap() {
-        allowance
-    } else {
-        0
-    }
+    e.storage().instance().get(&key).unwrap_or(0)
 }
 
 pub fn write_max_allowance(e: &Env, allowance: i128) {
@@ -1,24 +1,72 @@
-use soroban_sdk::{contract, contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Vec,
+};
 
 use crate::{
     access::{
-        check_zk_validation, has_owner, read_encrypted_keys, read_executor, read_executors_set,
-        read_is_executor, read_is_initialized, read_max_allowance, read_owner, write_controller,
-        write_executor, write_executors_set, write_is_initialized, write_max_allowance,
-        write_owner, write_passkey_hash, write_profile_encrypted,
+        increase_credential_epoch, read_controller, read_credential_epoch, read_encrypted_keys,
+        read_executor, read_executors_set, read_is_executor, read_is_initialized,
+        read_linked_profiles, read_max_allowance, read_pending_owner, remove_pending_owner,
+        remove_profile_encrypted, rotate_profile_encrypted, run_pkey, run_profile_pkey,
+        write_controller, write_executor, write_executors_set, write_is_initialized,
+        write_max_allowance, write_pending_owner, write_profile_encrypted, PkeyAuth,
+    },
+    approvals::{
+        approve_spender, read_approved_total, read_spender_approvals, record_spender_pulls,
+        revoke_all_spenders,
+    },
+    balance::{read_reserved, read_spendable, write_accounted, write_balance},
+    children::{child_send, create_child, read_child, read_children, revoke_child},
+    data::{
+        AuthPath, ChildSlot, EncryptedKeys, FailedAttempts, FeeConfig, FeeSpending,
+        InheritanceConfig, LinkedProfile, Memo, PaymentRequest, PendingOwner, PendingUpgrade,
+        ProfileEncrypted, Proposal, ProposalAction, ProposalStatus, QuestAction, QuestRule,
+        Redemption, RewardOffer, Signer, SignerCategory, SpenderApproval, Stream, StreamTerms,
+        Token, TokenMetadata, TransactionRecord, Vault, OWNER_TRANSFER_EXPIRY, UPGRADE_TIMELOCK,
+    },
+    events::{
+        credentials_rotated, owner_proposed, owner_transfer_cancelled, owner_transferred,
+        signer_updated, threshold_updated,
+    },
+    fees::{read_fee_config, read_fee_spending, write_fee_config},
+    inheritance::{
+        claim_inheritance, read_inheritance, read_last_activity, record_activity, write_inheritance,
+    },
+    lockout::{read_executor_failed_attempts, read_failed_attempts},
+    platform::{read_creation_platform, write_creation_platform},
+    proposals::{
+        approve_proposal, create_proposal, execute_proposal, read_proposal, read_proposal_count,
+        read_proposals, revoke_proposal,
+    },
+    requests::{cancel_request, create_request, read_request, read_request_count, settle_request},
+    rewards::{
+        read_redemption_count, read_redemptions, read_reward_offer, redeem_offer,
+        remove_reward_offer, write_reward_offer,
+    },
+    signers::{
+        add_signer, has_signers, read_owner, read_signer_weight, read_signers, read_threshold,
+        remove_signer, require_signers, update_signer_weight, write_owner, write_threshold,
+    },
+    streams::{
+        cancel_stream, create_stream, read_stream, read_stream_count, read_withdrawable,
+        stream_total, withdraw_stream,
     },
-    balance::{read_balance, write_balance},
-    data::{DataKey, EncryptedKeys, Token},
     tokens::{
-        read_has_been_added, read_token_count, read_tokens, save_token_id,
-        write_smart_transact_active, write_token_count,
+        add_token, native_token, read_token_metadata, read_tokens, write_smart_transact_active,
     },
     transact::{
-        increase_nonce, read_nonce, read_transact_no, read_tx_nonce, send_token, take_token,
-        write_tx_nonce,
+        credit_token, pull_token, read_nonce, read_transact_no, read_transactions, read_tx_nonce,
+        send_token, take_token, write_tx_nonce,
+    },
+    types::{Platform, UserPoints},
+    upgrade::{
+        migrate_legacy_profile, read_is_wasm_approved, read_pending_upgrade,
+        remove_pending_upgrade, upgrade_contract, write_pending_upgrade, write_wasm_approved,
     },
-    types::UserPoints,
-    user_quest::{read_quest_data, write_quest_data},
+    user_quest::{
+        read_quest_data, read_quest_rule, record_quest_action, write_quest_data, write_quest_rule,
+    },
+    vaults::{create_vault, deposit_vault, read_vault, read_vault_count, withdraw_vault},
 };
 
 pub trait SubAccountTrait {
@@ -26,70 +74,369 @@ pub trait SubAccountTrait {
     fn init_with_profile(
         e: Env,
         controller_id: Address,
-        platform: String,
+        platform: Platform,
         profile_id: String,
         salt: String,
         salt_iv: String,
         key_index: String,
         index_iv: String,
-        passkey_hash: String,
+        passkey: BytesN<65>,
         max_allowance: i128,
     );
     fn set_executor(e: Env, index: u32, executor: Address);
     fn set_executor_done(e: Env);
     fn update_user_points(e: Env, caller: Address, user_data: UserPoints);
-    fn set_allowance_with_addr(e: Env, allowance: i128);
+    fn set_quest_rule(e: Env, action: QuestAction, rule: QuestRule);
+    fn set_allowance_with_addr(e: Env, signers: Vec<Address>, allowance: i128);
     fn set_allowance_pkey(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
         allowance: i128,
+    ) -> bool;
+    fn set_owner_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        owner_id: Address,
+    ) -> bool;
+    fn receive(e: Env, from: Address, token_id: Address, amount: i128, memo: Memo);
+    fn send_auth_addr(
+        e: Env,
+        signers: Vec<Address>,
+        to: Address,
+        token_id: Address,
+        amount: i128,
+        memo: Memo,
     );
-    fn set_owner_pkey(e: Env, executor_index: u32, entered_passkey_hash: String, owner_id: Address);
-    fn receive(e: Env, from: Address, token_id: Address, amount: i128);
-    fn send_auth_addr(e: Env, to: Address, token_id: Address, amount: i128);
     fn send_with_pkey(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
         to: Address,
         token_id: Address,
         amount: i128,
+        memo: Memo,
+    ) -> bool;
+    fn approve_spender_addr(
+        e: Env,
+        signers: Vec<Address>,
+        token_id: Address,
+        spender: Address,
+        amount: i128,
+        approval_expiration_ledger: u32,
     );
-    fn set_smart_transact_addr(e: Env, token_id: Address, activate_disable: bool);
-    fn set_smart_transact_pkey(
+    fn approve_spender_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        token_id: Address,
+        spender: Address,
+        amount: i128,
+        approval_expiration_ledger: u32,
+    ) -> bool;
+    fn revoke_all_addr(e: Env, signer: Address);
+    fn revoke_all_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+    ) -> bool;
+    fn get_spender_approvals(e: Env) -> Vec<SpenderApproval>;
+    fn create_stream_addr(e: Env, signers: Vec<Address>, terms: StreamTerms) -> u32;
+    fn create_stream_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        terms: StreamTerms,
+    ) -> Option<u32>;
+    fn withdraw_stream(e: Env, stream_id: u32) -> i128;
+    fn cancel_stream_addr(e: Env, signers: Vec<Address>, stream_id: u32);
+    fn cancel_stream_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        stream_id: u32,
+    ) -> bool;
+    fn get_stream(e: Env, stream_id: u32) -> Option<Stream>;
+    fn get_stream_count(e: Env) -> u32;
+    fn get_stream_withdrawable(e: Env, stream_id: u32) -> i128;
+    fn get_reserved_balance(e: Env, token_id: Address) -> i128;
+    fn create_vault_addr(
+        e: Env,
+        signers: Vec<Address>,
+        name: String,
+        token_id: Address,
+        goal: i128,
+        lock_until_ledger: u32,
+    ) -> u32;
+    fn create_vault_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        name: String,
+        token_id: Address,
+        goal: i128,
+        lock_until_ledger: u32,
+    ) -> Option<u32>;
+    fn deposit_vault_addr(e: Env, signers: Vec<Address>, vault_id: u32, amount: i128);
+    fn deposit_vault_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        vault_id: u32,
+        amount: i128,
+    ) -> bool;
+    fn withdraw_vault_addr(e: Env, signers: Vec<Address>, vault_id: u32, amount: i128);
+    fn get_vault(e: Env, vault_id: u32) -> Option<Vault>;
+    fn get_vault_count(e: Env) -> u32;
+    fn create_child_addr(
+        e: Env,
+        signers: Vec<Address>,
+        signer: Address,
+        budgets: Map<Address, i128>,
+        child_expiration_ledger: u32,
+    ) -> u32;
+    fn create_child_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        signer: Address,
+        budgets: Map<Address, i128>,
+        child_expiration_ledger: u32,
+    ) -> Option<u32>;
+    fn child_send(e: Env, child_id: u32, to: Address, token_id: Address, amount: i128, memo: Memo);
+    fn revoke_child_addr(e: Env, signers: Vec<Address>, child_id: u32);
+    fn revoke_child_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        child_id: u32,
+    ) -> bool;
+    fn get_child(e: Env, child_id: u32) -> Option<ChildSlot>;
+    fn get_children(e: Env) -> Vec<ChildSlot>;
+    fn set_inheritance_addr(e: Env, signers: Vec<Address>, config: Option<InheritanceConfig>);
+    fn claim_inheritance(e: Env, claimant: Address, token_id: Address) -> i128;
+    fn get_inheritance(e: Env) -> Option<InheritanceConfig>;
+    fn get_last_activity(e: Env) -> u32;
+    fn create_request_addr(
+        e: Env,
+        signers: Vec<Address>,
+        token_id: Address,
+        amount: i128,
+        request_expiration_ledger: u32,
+        memo: Memo,
+    ) -> u32;
+    fn create_request_pkey(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        token_id: Address,
+        amount: i128,
+        request_expiration_ledger: u32,
+        memo: Memo,
+    ) -> Option<u32>;
+    fn pay_request(e: Env, payer: Address, request_id: u32);
+    fn cancel_request_addr(e: Env, signers: Vec<Address>, request_id: u32);
+    fn cancel_request_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        request_id: u32,
+    ) -> bool;
+    fn get_payment_request(e: Env, request_id: u32) -> Option<PaymentRequest>;
+    fn get_payment_request_count(e: Env) -> u32;
+    fn set_smart_transact_addr(
+        e: Env,
+        signers: Vec<Address>,
         token_id: Address,
         activate_disable: bool,
     );
+    fn set_smart_transact_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        token_id: Address,
+        activate_disable: bool,
+    ) -> bool;
     fn create_tx_nonce(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
         spender: Address,
         token_id: Address,
         amount: i128,
-    );
+    ) -> bool;
+    fn pull_deposit(e: Env, from: Address, token_id: Address, amount: i128, memo: Memo);
+    fn credit_deposit(e: Env, token_id: Address) -> i128;
     fn set_dashboard_balance(e: Env, caller: Address, token_id: Address, amount: i128);
     fn clear_tx_nonce(e: Env);
     fn get_tx_count(e: Env) -> u32;
     fn get_owner(e: Env) -> Address;
-    fn get_encrypted_keys(e: Env, profile_id: String) -> EncryptedKeys;
+    fn get_transactions(e: Env, offset: u32, limit: u32) -> Vec<TransactionRecord>;
+    fn add_signer(e: Env, signers: Vec<Address>, signer: Address, weight: u32);
+    fn remove_signer(e: Env, signers: Vec<Address>, signer: Address);
+    fn set_signer_weight(e: Env, signers: Vec<Address>, signer: Address, weight: u32);
+    fn set_threshold(e: Env, signers: Vec<Address>, category: SignerCategory, threshold: u32);
+    fn get_signers(e: Env) -> Vec<Signer>;
+    fn get_signer_weight(e: Env, address: Address) -> u32;
+    fn get_threshold(e: Env, category: SignerCategory) -> u32;
+    fn propose_owner(e: Env, signers: Vec<Address>, new_owner: Address);
+    fn accept_owner(e: Env);
+    fn cancel_owner_transfer(e: Env, signers: Vec<Address>);
+    fn get_pending_owner(e: Env) -> Option<PendingOwner>;
+    fn propose_transaction(e: Env, proposer: Address, action: ProposalAction) -> u32;
+    fn approve_proposal(e: Env, signer: Address, proposal_id: u32);
+    fn execute_proposal(e: Env, proposal_id: u32);
+    fn revoke_proposal(e: Env, proposer: Address, proposal_id: u32);
+    fn get_proposal(e: Env, proposal_id: u32) -> Option<Proposal>;
+    fn get_proposal_count(e: Env) -> u32;
+    fn get_proposals(e: Env, status: ProposalStatus, start: u32, limit: u32) -> Vec<Proposal>;
+    fn link_profile_addr(
+        e: Env,
+        signers: Vec<Address>,
+        profile: ProfileEncrypted,
+        passkey: BytesN<65>,
+    );
+    fn link_profile_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        profile: ProfileEncrypted,
+        passkey: BytesN<65>,
+    ) -> bool;
+    fn unlink_profile_addr(e: Env, signers: Vec<Address>, platform: Platform, profile_id: String);
+    fn unlink_profile_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        platform: Platform,
+        profile_id: String,
+    ) -> bool;
+    fn rotate_credentials_addr(
+        e: Env,
+        signers: Vec<Address>,
+        profile: ProfileEncrypted,
+        passkey: BytesN<65>,
+    );
+    fn rotate_credentials_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        profile: ProfileEncrypted,
+        passkey: BytesN<65>,
+    ) -> bool;
+    fn get_credential_epoch(e: Env) -> u32;
+    fn get_failed_attempts(e: Env) -> FailedAttempts;
+    fn get_executor_failed_attempts(e: Env, executor: Address) -> FailedAttempts;
+    fn get_linked_profiles(e: Env) -> Vec<LinkedProfile>;
+    fn get_encrypted_keys(e: Env, platform: Platform, profile_id: String) -> EncryptedKeys;
+    fn get_creation_platform(e: Env) -> Platform;
     fn get_executor(e: Env, index: u32) -> Address;
     fn get_tokens(e: Env) -> Vec<Token>;
     fn get_balance(e: Env, token_id: Address) -> i128;
-    fn get_nonce(e: Env) -> u32;
+    fn get_token_metadata(e: Env, token_id: Address) -> TokenMetadata;
+    fn get_native_token(e: Env) -> Address;
+    fn get_nonce(e: Env, platform: Platform, profile_id: String) -> u32;
     fn get_tx_nonce(e: Env) -> Bytes;
     fn get_user_points(e: Env) -> UserPoints;
+    fn get_quest_rule(e: Env, action: QuestAction) -> QuestRule;
+    fn set_reward_offer(e: Env, offer_id: u32, offer: RewardOffer);
+    fn remove_reward_offer(e: Env, offer_id: u32);
+    fn redeem_points_addr(e: Env, signers: Vec<Address>, offer_id: u32);
+    fn redeem_points_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        offer_id: u32,
+    ) -> bool;
+    fn get_reward_offer(e: Env, offer_id: u32) -> Option<RewardOffer>;
+    fn get_redemption_count(e: Env) -> u32;
+    fn get_redemptions(e: Env, start: u32, limit: u32) -> Vec<Redemption>;
     fn get_allowance(e: Env) -> i128;
-    fn upgrade(e: Env, new_wasm_hash: BytesN<32>);
+    fn set_fee_config_addr(e: Env, signers: Vec<Address>, config: Option<FeeConfig>);
+    fn set_fee_config_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        config: Option<FeeConfig>,
+    ) -> bool;
+    fn get_fee_config(e: Env) -> Option<FeeConfig>;
+    fn get_fee_spending(e: Env) -> FeeSpending;
+    fn set_wasm_approved(e: Env, wasm_hash: BytesN<32>, approved: bool);
+    fn is_wasm_approved(e: Env, wasm_hash: BytesN<32>) -> bool;
+    fn upgrade(e: Env, signers: Vec<Address>, new_wasm_hash: BytesN<32>);
+    fn upgrade_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        new_wasm_hash: BytesN<32>,
+    ) -> bool;
+    fn propose_upgrade(e: Env, new_wasm_hash: BytesN<32>);
+    fn execute_upgrade(e: Env);
+    fn cancel_upgrade_addr(e: Env, signers: Vec<Address>);
+    fn cancel_upgrade_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+    ) -> bool;
+    fn get_pending_upgrade(e: Env) -> Option<PendingUpgrade>;
+    fn migrate_profile(e: Env, platform: Platform, passkey: BytesN<65>);
 }
 
 #[contract]
 pub struct SubAccount;
 
+//Bumps the credential epoch so nonces and sessions created with the old credentials are invalid
+fn rotate_credentials(e: &Env, platform: Platform, profile_id: String) {
+    let epoch = increase_credential_epoch(e);
+    write_tx_nonce(e, Bytes::new(e));
+    credentials_rotated(e, platform, profile_id, epoch);
+}
+
 #[contractimpl]
 impl SubAccountTrait for SubAccount {
     //Initialization of smart wallet when it is created using an external account
@@ -101,16 +448,18 @@ impl SubAccountTrait for SubAccount {
         }
         write_owner(&e, &owner_id);
         write_controller(&e, &controller_id);
+        write_creation_platform(&e, Platform::Stellar);
 
         let user_points = UserPoints {
             has_set_signer: false,
             has_set_allowance: false,
             has_received: false,
             has_sent: false,
-            points: 2000,
+            points: 0,
         };
 
         write_quest_data(&e, user_points);
+        record_quest_action(&e, QuestAction::Init, 0);
 
         write_is_initialized(&e)
     }
@@ -119,13 +468,13 @@ impl SubAccountTrait for SubAccount {
     fn init_with_profile(
         e: Env,
         controller_id: Address,
-        platform: String,
+        platform: Platform,
         profile_id: String,
         salt: String,
         salt_iv: String,
         key_index: String,
         index_iv: String,
-        passkey_hash: String,
+        passkey: BytesN<65>,
         max_allowance: i128,
     ) {
         let is_initialized = read_is_initialized(&e);
@@ -133,22 +482,30 @@ impl SubAccountTrait for SubAccount {
         if is_initialized {
             panic!("has already been initilized")
         }
-        // write_id(&e, platform, profile_id, encrypted_token, encrypted_index);
         write_controller(&e, &controller_id);
-        write_passkey_hash(&e, passkey_hash);
-        write_profile_encrypted(&e, platform, profile_id, salt, salt_iv, key_index, index_iv);
+        write_creation_platform(&e, platform);
+        let profile = ProfileEncrypted {
+            platform,
+            profile_id,
+            gen_salt_encrypted: salt,
+            salt_encryption_iv: salt_iv,
+            index_encrypted: key_index,
+            index_encryption_iv: index_iv,
+        };
+        write_profile_encrypted(&e, profile, passkey);
         write_max_allowance(&e, max_allowance);
 
-        //initialize point data, creating account with twitter earns 2000 points
+        //initialize point data, creating the account earns the init reward
         let user_points = UserPoints {
             has_set_signer: false,
             has_set_allowance: false,
             has_received: false,
             has_sent: false,
-            points: 2000,
+            points: 0,
         };
 
         write_quest_data(&e, user_points);
+        record_quest_action(&e, QuestAction::Init, 0);
 
         write_is_initialized(&e)
     }
@@ -179,11 +536,11 @@ impl SubAccountTrait for SubAccount {
     //For programed transactions
 
     //This set allowance using the external account used to create the smart account
-    //only the owner(wallet that created the account) can invoke this
-    fn set_allowance_with_addr(e: Env, allowance: i128) {
-        let owner = read_owner(&e).expect("No owner address found");
-        owner.require_auth();
+    //the listed signers must meet the config threshold
+    fn set_allowance_with_addr(e: Env, signers: Vec<Address>, allowance: i128) {
+        require_signers(&e, SignerCategory::Config, signers);
         write_max_allowance(&e, allowance);
+        record_quest_action(&e, QuestAction::SetAllowance, 0);
     }
 
     //This set allowance using the social media auth credentials that were used to create the smart account
@@ -191,21 +548,17 @@ impl SubAccountTrait for SubAccount {
     fn set_allowance_pkey(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
         allowance: i128,
-    ) {
-        let authorized = check_zk_validation(&e, entered_passkey_hash);
-        if !authorized {
-            panic!("Not authorized to invoke this function")
-        }
-
-        let executor = read_executor(&e, executor_index);
-        executor.require_auth();
-        write_max_allowance(&e, allowance);
-        let mut user_data = read_quest_data(&e);
-        user_data.has_set_allowance = true;
-        user_data.points += 500;
-        write_quest_data(&e, user_data);
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "set_allowance_pkey", allowance, || {
+            write_max_allowance(&e, allowance);
+            record_quest_action(&e, QuestAction::SetAllowance, 0);
+        })
+        .is_some()
     }
 
     //set the owners external wallet for a smart account created with twitter
@@ -213,88 +566,110 @@ impl SubAccountTrait for SubAccount {
     fn set_owner_pkey(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
         owner_id: Address,
-    ) {
-        let executor = read_executor(&e, executor_index);
-        executor.require_auth();
-        let has_owner = has_owner(&e);
-        if has_owner {
+    ) -> bool {
+        if has_signers(&e) {
             panic!("Owner has already been set")
         }
 
-        let authorized = check_zk_validation(&e, entered_passkey_hash);
-        if !authorized {
-            panic!("Not authorized to invoke this function")
-        }
-
-        write_owner(&e, &owner_id);
-        let mut user_data = read_quest_data(&e);
-        user_data.has_set_signer = true;
-        user_data.points += 500;
-        write_quest_data(&e, user_data);
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "set_owner_pkey", owner_id.clone(), || {
+            write_owner(&e, &owner_id);
+            record_quest_action(&e, QuestAction::SetSigner, 0);
+        })
+        .is_some()
     }
 
     //This allow users to send funds from an external wallet to the smart wallet.
     // Receive is in respect to the smart wallet (funds are received by the smart wallet)
 
-    fn receive(e: Env, from: Address, token_id: Address, amount: i128) {
+    fn receive(e: Env, from: Address, token_id: Address, amount: i128, memo: Memo) {
         from.require_auth();
-        take_token(&e, &from, &token_id, amount);
+        take_token(&e, &from, &token_id, amount, AuthPath::Depositor, memo);
         write_balance(&e, token_id.clone(), amount);
-        let has_been_added = read_has_been_added(&e, token_id.clone());
-        if !has_been_added {
-            let key = DataKey::TokenAdded(token_id.clone());
-            e.storage().instance().set(&key, &true);
-            let new_count = read_token_count(&e) + 1;
-            save_token_id(&e, new_count, token_id);
-            write_token_count(&e, new_count)
-        }
+        add_token(&e, token_id);
 
-        let mut user_data = read_quest_data(&e);
-        user_data.has_received = true;
-        user_data.points += 250;
-        write_quest_data(&e, user_data);
+        record_quest_action(&e, QuestAction::Receive, amount);
+    }
+
+    //The controller pulls a deposit the depositor approved the sub-account to spend with transfer_from
+
+    fn pull_deposit(e: Env, from: Address, token_id: Address, amount: i128, memo: Memo) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        pull_token(&e, &from, &token_id, amount, AuthPath::Controller, memo);
+        write_balance(&e, token_id.clone(), amount);
+        add_token(&e, token_id);
+
+        record_quest_action(&e, QuestAction::Receive, amount);
+    }
+
+    //Credits tokens transferred straight to the account address, anyone can call it
+    //returns the amount credited
+
+    fn credit_deposit(e: Env, token_id: Address) -> i128 {
+        record_spender_pulls(&e);
+        let amount = credit_token(&e, &token_id);
+        write_balance(&e, token_id.clone(), amount);
+        add_token(&e, token_id);
+
+        record_quest_action(&e, QuestAction::Receive, amount);
+        amount
     }
 
     fn set_dashboard_balance(e: Env, caller: Address, token_id: Address, amount: i128) {
+        caller.require_auth();
         let is_executor = read_is_executor(&e, caller);
 
-        if is_executor == false {
+        if !is_executor {
             panic!("caller is not an executor")
         }
 
+        //the accounted balance is stored before the adjustment so it does not start from it
+        write_accounted(&e, token_id.clone(), 0);
         write_balance(&e, token_id.clone(), amount);
-        let has_been_added = read_has_been_added(&e, token_id.clone());
-        if !has_been_added {
-            let key = DataKey::TokenAdded(token_id.clone());
-            e.storage().instance().set(&key, &true);
-            let new_count = read_token_count(&e) + 1;
-            save_token_id(&e, new_count, token_id);
-            write_token_count(&e, new_count)
-        }
+        add_token(&e, token_id);
     }
 
     //Updates users quest data and onchain activities points
 
     fn update_user_points(e: Env, caller: Address, user_data: UserPoints) {
+        caller.require_auth();
         let is_executor = read_is_executor(&e, caller);
 
-        if is_executor == false {
+        if !is_executor {
             panic!("caller is not an executor")
         }
 
         write_quest_data(&e, user_data);
     }
 
+    //The controller configures the points, repeatability, minimum amount and cooldown of each action
+
+    fn set_quest_rule(e: Env, action: QuestAction, rule: QuestRule) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        write_quest_rule(&e, action, rule);
+    }
+
     //this allow the owner (external account that created the wallet) to send funds from the smart wallet
-    //to an external account
+    //to an external account, the listed signers must meet the transfer threshold
 
-    fn send_auth_addr(e: Env, to: Address, token_id: Address, amount: i128) {
-        let owner = read_owner(&e).expect("Owner not set");
-        owner.require_auth();
-        send_token(&e, &to, &token_id, amount);
-        write_balance(&e, token_id, -amount)
+    fn send_auth_addr(
+        e: Env,
+        signers: Vec<Address>,
+        to: Address,
+        token_id: Address,
+        amount: i128,
+        memo: Memo,
+    ) {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        send_token(&e, &to, &token_id, amount, AuthPath::Owner, memo);
+        write_balance(&e, token_id, -amount);
+        record_quest_action(&e, QuestAction::Send, amount);
     }
 
     //this allow the owner to send funds from the smart wallet, the owner authenticate using
@@ -303,36 +678,423 @@ impl SubAccountTrait for SubAccount {
     fn send_with_pkey(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
         to: Address,
         token_id: Address,
         amount: i128,
+        memo: Memo,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        let args = (to.clone(), token_id.clone(), amount, memo.clone());
+        run_pkey(&e, auth, "send_with_pkey", args, || {
+            let allowance = read_max_allowance(&e);
+            if amount > allowance {
+                panic!("You cannot send an amount greater than your allowance")
+            }
+
+            write_balance(&e, token_id.clone(), -amount);
+            send_token(&e, &to, &token_id, amount, AuthPath::Pkey, memo);
+            record_quest_action(&e, QuestAction::Send, amount);
+        })
+        .is_some()
+    }
+
+    //Lets a third party spend tokens of the sub-account with transfer_from, the approval is made
+    //by the sub-account itself and tracked so it can be revoked, amount zero removes it
+
+    fn approve_spender_addr(
+        e: Env,
+        signers: Vec<Address>,
+        token_id: Address,
+        spender: Address,
+        amount: i128,
+        approval_expiration_ledger: u32,
     ) {
-        let authorized = check_zk_validation(&e, entered_passkey_hash);
-        if !authorized {
-            panic!("Not authorized to invoke this function")
-        }
-        let allowance = read_max_allowance(&e);
-        if amount > allowance {
-            panic!("You cannot send an amount greater than your allowance")
-        }
-        let caller_id = read_executor(&e, executor_index);
-        let is_executor = read_is_executor(&e, caller_id);
+        require_signers(&e, SignerCategory::Transfer, signers);
+        approve_spender(&e, token_id, spender, amount, approval_expiration_ledger);
+    }
+
+    //Same as approve_spender_addr with social credentials, limited to the allowance like send_with_pkey
 
-        if is_executor == false {
-            panic!("Caller is not an executor")
+    fn approve_spender_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        token_id: Address,
+        spender: Address,
+        amount: i128,
+        approval_expiration_ledger: u32,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        let args = (
+            token_id.clone(),
+            spender.clone(),
+            amount,
+            approval_expiration_ledger,
+        );
+        run_pkey(&e, auth, "approve_spender_pkey", args, || {
+            //approvals granted with a passkey add up against the allowance
+            let approved = read_approved_total(&e, &token_id, Some(&spender)) + amount;
+            if approved > read_max_allowance(&e) {
+                panic!("You cannot approve an amount greater than your allowance")
+            }
+
+            approve_spender(&e, token_id, spender, amount, approval_expiration_ledger);
+        })
+        .is_some()
+    }
+
+    //Incident response, revokes every tracked spender approval
+    //a single signer is enough since revoking can only reduce what can be spent
+
+    fn revoke_all_addr(e: Env, signer: Address) {
+        signer.require_auth();
+        if read_signer_weight(&e, &signer) == 0 {
+            panic!("Address is not a signer")
         }
+        record_activity(&e);
+        revoke_all_spenders(&e);
+    }
+
+    fn revoke_all_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "revoke_all_pkey", (), || revoke_all_spenders(&e)).is_some()
+    }
 
-        write_balance(&e, token_id.clone(), -amount);
-        send_token(&e, &to, &token_id, amount);
+    fn get_spender_approvals(e: Env) -> Vec<SpenderApproval> {
+        read_spender_approvals(&e)
+    }
+
+    //Streams tokens to a recipient at a rate per ledger, the whole stream amount is reserved
+    //so sends cannot spend it, the recipient withdraws what has accrued at any time
+
+    fn create_stream_addr(e: Env, signers: Vec<Address>, terms: StreamTerms) -> u32 {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        create_stream(&e, terms)
+    }
+
+    fn create_stream_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        terms: StreamTerms,
+    ) -> Option<u32> {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "create_stream_pkey", terms.clone(), || {
+            if stream_total(&terms) > read_max_allowance(&e) {
+                panic!("You cannot stream an amount greater than your allowance")
+            }
+
+            create_stream(&e, terms)
+        })
+    }
+
+    fn withdraw_stream(e: Env, stream_id: u32) -> i128 {
+        withdraw_stream(&e, stream_id)
+    }
+
+    //Cancelling pays the recipient what has accrued and gives the rest back to the account
+
+    fn cancel_stream_addr(e: Env, signers: Vec<Address>, stream_id: u32) {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        cancel_stream(&e, stream_id);
+    }
+
+    fn cancel_stream_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        stream_id: u32,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "cancel_stream_pkey", stream_id, || {
+            cancel_stream(&e, stream_id)
+        })
+        .is_some()
+    }
+
+    fn get_stream(e: Env, stream_id: u32) -> Option<Stream> {
+        read_stream(&e, stream_id)
+    }
+
+    fn get_stream_count(e: Env) -> u32 {
+        read_stream_count(&e)
+    }
+
+    fn get_stream_withdrawable(e: Env, stream_id: u32) -> i128 {
+        let stream = read_stream(&e, stream_id).expect("Stream not found");
+        read_withdrawable(&e, &stream)
+    }
+
+    //Amount of a token that sends cannot spend because it is reserved
+    fn get_reserved_balance(e: Env, token_id: Address) -> i128 {
+        read_reserved(&e, token_id)
+    }
+
+    //Savings vaults set part of a token balance aside, funds in a vault are no longer part of
+    //the spendable balance so executors cannot send them
+
+    fn create_vault_addr(
+        e: Env,
+        signers: Vec<Address>,
+        name: String,
+        token_id: Address,
+        goal: i128,
+        lock_until_ledger: u32,
+    ) -> u32 {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        create_vault(&e, name, token_id, goal, lock_until_ledger)
+    }
+
+    fn create_vault_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        name: String,
+        token_id: Address,
+        goal: i128,
+        lock_until_ledger: u32,
+    ) -> Option<u32> {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(
+            &e,
+            auth,
+            "create_vault_pkey",
+            (name.clone(), token_id.clone(), goal, lock_until_ledger),
+            || create_vault(&e, name, token_id, goal, lock_until_ledger),
+        )
+    }
+
+    fn deposit_vault_addr(e: Env, signers: Vec<Address>, vault_id: u32, amount: i128) {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        deposit_vault(&e, vault_id, amount);
+    }
+
+    fn deposit_vault_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        vault_id: u32,
+        amount: i128,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "deposit_vault_pkey", (vault_id, amount), || {
+            deposit_vault(&e, vault_id, amount)
+        })
+        .is_some()
+    }
+
+    //Only the owner can take funds out of a vault, there is no pkey path for executors
+
+    fn withdraw_vault_addr(e: Env, signers: Vec<Address>, vault_id: u32, amount: i128) {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        withdraw_vault(&e, vault_id, amount);
+    }
+
+    fn get_vault(e: Env, vault_id: u32) -> Option<Vault> {
+        read_vault(&e, vault_id)
+    }
+
+    fn get_vault_count(e: Env) -> u32 {
+        read_vault_count(&e)
+    }
+
+    //Child slots give a member their own signer and a budget per token, spending from the
+    //parent balance without sharing the parent credentials
+
+    fn create_child_addr(
+        e: Env,
+        signers: Vec<Address>,
+        signer: Address,
+        budgets: Map<Address, i128>,
+        child_expiration_ledger: u32,
+    ) -> u32 {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        create_child(&e, signer, budgets, child_expiration_ledger)
+    }
+
+    fn create_child_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        signer: Address,
+        budgets: Map<Address, i128>,
+        child_expiration_ledger: u32,
+    ) -> Option<u32> {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        let args = (signer.clone(), budgets.clone(), child_expiration_ledger);
+        run_pkey(&e, auth, "create_child_pkey", args, || {
+            let allowance = read_max_allowance(&e);
+            if budgets.values().iter().any(|budget| budget > allowance) {
+                panic!("You cannot give a budget greater than your allowance")
+            }
+
+            create_child(&e, signer, budgets, child_expiration_ledger)
+        })
+    }
+
+    fn child_send(e: Env, child_id: u32, to: Address, token_id: Address, amount: i128, memo: Memo) {
+        child_send(&e, child_id, to, token_id, amount, memo);
+        record_quest_action(&e, QuestAction::Send, amount);
+    }
+
+    //The parent can revoke a child slot at any time
+
+    fn revoke_child_addr(e: Env, signers: Vec<Address>, child_id: u32) {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        revoke_child(&e, child_id);
+    }
+
+    fn revoke_child_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        child_id: u32,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "revoke_child_pkey", child_id, || {
+            revoke_child(&e, child_id)
+        })
+        .is_some()
+    }
+
+    fn get_child(e: Env, child_id: u32) -> Option<ChildSlot> {
+        read_child(&e, child_id)
+    }
+
+    fn get_children(e: Env) -> Vec<ChildSlot> {
+        read_children(&e)
+    }
+
+    //Dead-man's switch, the beneficiaries can claim the funds once the account has had no owner
+    //or pkey activity for the inactivity period plus the grace period, any activity restarts it
+    //beneficiaries can only be changed by signers meeting the config threshold
+
+    fn set_inheritance_addr(e: Env, signers: Vec<Address>, config: Option<InheritanceConfig>) {
+        require_signers(&e, SignerCategory::Config, signers);
+        write_inheritance(&e, config);
+    }
+
+    //Pays every beneficiary their share of a token, returns the amount paid out
+
+    fn claim_inheritance(e: Env, claimant: Address, token_id: Address) -> i128 {
+        claim_inheritance(&e, claimant, token_id)
+    }
+
+    fn get_inheritance(e: Env) -> Option<InheritanceConfig> {
+        read_inheritance(&e)
+    }
+
+    //Ledger of the last owner or pkey authorized action
+    fn get_last_activity(e: Env) -> u32 {
+        read_last_activity(&e)
+    }
+
+    //Payment requests let a merchant account ask for an amount of a token, the request id
+    //is shared with the payer for checkout
+
+    fn create_request_addr(
+        e: Env,
+        signers: Vec<Address>,
+        token_id: Address,
+        amount: i128,
+        request_expiration_ledger: u32,
+        memo: Memo,
+    ) -> u32 {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        create_request(&e, token_id, amount, request_expiration_ledger, memo)
+    }
+
+    fn create_request_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        token_id: Address,
+        amount: i128,
+        request_expiration_ledger: u32,
+        memo: Memo,
+    ) -> Option<u32> {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        let args = (
+            token_id.clone(),
+            amount,
+            request_expiration_ledger,
+            memo.clone(),
+        );
+        run_pkey(&e, auth, "create_request_pkey", args, || {
+            create_request(&e, token_id, amount, request_expiration_ledger, memo)
+        })
+    }
+
+    //Any payer settles an open request, the payment goes through receive with the request memo
+
+    fn pay_request(e: Env, payer: Address, request_id: u32) {
+        let request = settle_request(&e, request_id, payer.clone());
+        Self::receive(e, payer, request.token_id, request.amount, request.memo);
+    }
+
+    fn cancel_request_addr(e: Env, signers: Vec<Address>, request_id: u32) {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        cancel_request(&e, request_id);
+    }
+
+    fn cancel_request_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        request_id: u32,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "cancel_request_pkey", request_id, || {
+            cancel_request(&e, request_id)
+        })
+        .is_some()
+    }
+
+    fn get_payment_request(e: Env, request_id: u32) -> Option<PaymentRequest> {
+        read_request(&e, request_id)
+    }
+
+    fn get_payment_request_count(e: Env) -> u32 {
+        read_request_count(&e)
     }
 
     //This allows the owner to enable smart transaction for a token with balance greater than zero
     //for this, the owner is the extenal account that created it
 
-    fn set_smart_transact_addr(e: Env, token_id: Address, activate_disable: bool) {
-        let owner = read_owner(&e).expect("Owner not set");
-        owner.require_auth();
+    fn set_smart_transact_addr(
+        e: Env,
+        signers: Vec<Address>,
+        token_id: Address,
+        activate_disable: bool,
+    ) {
+        require_signers(&e, SignerCategory::Config, signers);
         write_smart_transact_active(&e, token_id, activate_disable);
     }
 
@@ -342,48 +1104,153 @@ impl SubAccountTrait for SubAccount {
     fn set_smart_transact_pkey(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
         token_id: Address,
         activate_disable: bool,
-    ) {
-        let authorized = check_zk_validation(&e, entered_passkey_hash);
-        if !authorized {
-            panic!("Not authorized to invoke this function")
-        }
-
-        let executor = read_executor(&e, executor_index);
-        executor.require_auth();
-        write_smart_transact_active(&e, token_id, activate_disable);
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(
+            &e,
+            auth,
+            "set_smart_transact_pkey",
+            (token_id.clone(), activate_disable),
+            || write_smart_transact_active(&e, token_id, activate_disable),
+        )
+        .is_some()
     }
 
     // this must run before any transaction can run
     fn create_tx_nonce(
         e: Env,
         executor_index: u32,
-        entered_passkey_hash: String,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
         spender: Address,
         token_id: Address,
         amount: i128,
-    ) {
-        let authorized = check_zk_validation(&e, entered_passkey_hash.clone());
-        if !authorized {
-            panic!("Not authorized to invoke this function")
-        }
-
-        let executor = read_executor(&e, executor_index);
-        executor.require_auth();
-        let pre_nonce = read_nonce(&e);
-        let seq_nonce = pre_nonce + 1;
-        increase_nonce(&e, 1);
+    ) -> bool {
         let mut salt = Bytes::new(&e);
-        salt.append(&executor.to_xdr(&e));
-        salt.append(&seq_nonce.to_xdr(&e));
-        salt.append(&entered_passkey_hash.to_xdr(&e));
-        salt.append(&spender.to_xdr(&e));
-        salt.append(&token_id.to_xdr(&e));
+        salt.append(&read_executor(&e, executor_index).to_xdr(&e));
+        salt.append(&nonce.to_xdr(&e));
+        salt.append(&read_credential_epoch(&e).to_xdr(&e));
+        salt.append(&passkey_proof.clone().to_xdr(&e));
+        salt.append(&spender.clone().to_xdr(&e));
+        salt.append(&token_id.clone().to_xdr(&e));
         salt.append(&amount.to_xdr(&e));
-        let new_nonce = e.crypto().sha256(&salt).to_xdr(&e);
-        write_tx_nonce(&e, new_nonce);
+
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(
+            &e,
+            auth,
+            "create_tx_nonce",
+            (spender, token_id, amount),
+            || {
+                let new_nonce = e.crypto().sha256(&salt).to_xdr(&e);
+                write_tx_nonce(&e, new_nonce);
+            },
+        )
+        .is_some()
+    }
+
+    //Links an additional social profile with its own encrypted keys and passkey
+    //authorized by the owner external account
+
+    fn link_profile_addr(
+        e: Env,
+        signers: Vec<Address>,
+        profile: ProfileEncrypted,
+        passkey: BytesN<65>,
+    ) {
+        require_signers(&e, SignerCategory::Config, signers);
+        write_profile_encrypted(&e, profile, passkey);
+    }
+
+    //Links an additional social profile, authorized with the credentials of an already linked profile
+
+    fn link_profile_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        profile: ProfileEncrypted,
+        passkey: BytesN<65>,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(
+            &e,
+            auth,
+            "link_profile_pkey",
+            (profile.clone(), passkey.clone()),
+            || write_profile_encrypted(&e, profile, passkey),
+        )
+        .is_some()
+    }
+
+    fn unlink_profile_addr(e: Env, signers: Vec<Address>, platform: Platform, profile_id: String) {
+        require_signers(&e, SignerCategory::Config, signers);
+        remove_profile_encrypted(&e, platform, profile_id);
+    }
+
+    fn unlink_profile_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        platform: Platform,
+        profile_id: String,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(
+            &e,
+            auth,
+            "unlink_profile_pkey",
+            (platform, profile_id.clone()),
+            || remove_profile_encrypted(&e, platform, profile_id),
+        )
+        .is_some()
+    }
+
+    //Replaces the passkey and encrypted keys of a linked profile, authorized by the owner external account
+
+    fn rotate_credentials_addr(
+        e: Env,
+        signers: Vec<Address>,
+        profile: ProfileEncrypted,
+        passkey: BytesN<65>,
+    ) {
+        require_signers(&e, SignerCategory::Config, signers);
+        rotate_profile_encrypted(&e, profile.clone(), passkey);
+        rotate_credentials(&e, profile.platform, profile.profile_id);
+    }
+
+    //Replaces the passkey and encrypted keys of a linked profile, authorized with the current
+    //passkey of that same profile
+
+    fn rotate_credentials_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        profile: ProfileEncrypted,
+        passkey: BytesN<65>,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        let linked = LinkedProfile {
+            platform: profile.platform,
+            profile_id: profile.profile_id.clone(),
+        };
+        let args = (profile.clone(), passkey.clone());
+        run_profile_pkey(&e, auth, linked, "rotate_credentials_pkey", args, || {
+            rotate_profile_encrypted(&e, profile.clone(), passkey);
+            rotate_credentials(&e, profile.platform, profile.profile_id);
+        })
+        .is_some()
     }
 
     fn clear_tx_nonce(e: Env) {
@@ -395,35 +1262,184 @@ impl SubAccountTrait for SubAccount {
         read_transact_no(&e)
     }
 
-    //Gets the smart account owner
+    //Gets the smart account owner, only set while the account has a single signer
     fn get_owner(e: Env) -> Address {
         read_owner(&e).expect("Owner not found!")
     }
 
+    //Recent token transfers of the account, newest first, offset skips the most recent ones
+    //only the last MAX_TRANSACTION_HISTORY transfers are kept
+    fn get_transactions(e: Env, offset: u32, limit: u32) -> Vec<TransactionRecord> {
+        read_transactions(&e, offset, limit)
+    }
+
+    //Adds, removes or reweights a signer, approved by signers meeting the config threshold
+    //every threshold must remain reachable by the resulting signer set
+
+    fn add_signer(e: Env, signers: Vec<Address>, signer: Address, weight: u32) {
+        require_signers(&e, SignerCategory::Config, signers);
+        add_signer(&e, signer.clone(), weight);
+        signer_updated(&e, signer, weight);
+    }
+
+    fn remove_signer(e: Env, signers: Vec<Address>, signer: Address) {
+        require_signers(&e, SignerCategory::Config, signers);
+        remove_signer(&e, signer.clone());
+        signer_updated(&e, signer, 0);
+    }
+
+    fn set_signer_weight(e: Env, signers: Vec<Address>, signer: Address, weight: u32) {
+        require_signers(&e, SignerCategory::Config, signers);
+        update_signer_weight(&e, signer.clone(), weight);
+        signer_updated(&e, signer, weight);
+    }
+
+    fn set_threshold(e: Env, signers: Vec<Address>, category: SignerCategory, threshold: u32) {
+        require_signers(&e, SignerCategory::Config, signers);
+        write_threshold(&e, category, threshold);
+        threshold_updated(&e, category, threshold);
+    }
+
+    //Gets the smart account signers, a single owner account has one signer with weight 1
+    fn get_signers(e: Env) -> Vec<Signer> {
+        read_signers(&e)
+    }
+
+    fn get_signer_weight(e: Env, address: Address) -> u32 {
+        read_signer_weight(&e, &address)
+    }
+
+    fn get_threshold(e: Env, category: SignerCategory) -> u32 {
+        read_threshold(&e, category)
+    }
+
+    //Starts moving ownership to a new wallet, the new owner has to accept before the proposal expires
+    //once accepted the new owner replaces the whole signer set, a new proposal replaces the pending one
+
+    fn propose_owner(e: Env, signers: Vec<Address>, new_owner: Address) {
+        require_signers(&e, SignerCategory::Config, signers);
+        if read_owner(&e) == Some(new_owner.clone()) {
+            panic!("Address is already the owner")
+        }
+        let pending = PendingOwner {
+            new_owner: new_owner.clone(),
+            expiration_ledger: e.ledger().sequence() + OWNER_TRANSFER_EXPIRY,
+        };
+        write_pending_owner(&e, &pending);
+        owner_proposed(&e, new_owner, pending.expiration_ledger);
+    }
+
+    fn accept_owner(e: Env) {
+        let pending = read_pending_owner(&e).expect("No pending owner transfer");
+        pending.new_owner.require_auth();
+        if e.ledger().sequence() > pending.expiration_ledger {
+            panic!("Owner transfer has expired")
+        }
+        write_owner(&e, &pending.new_owner);
+        remove_pending_owner(&e);
+        record_activity(&e);
+        owner_transferred(&e, pending.new_owner);
+    }
+
+    fn cancel_owner_transfer(e: Env, signers: Vec<Address>) {
+        require_signers(&e, SignerCategory::Config, signers);
+        let pending = read_pending_owner(&e).expect("No pending owner transfer");
+        remove_pending_owner(&e);
+        owner_transfer_cancelled(&e, pending.new_owner);
+    }
+
+    fn get_pending_owner(e: Env) -> Option<PendingOwner> {
+        read_pending_owner(&e)
+    }
+
+    //Any signer can propose a transfer or contract call from the sub-account, other signers
+    //approve it over time and anyone can execute it once the approvals meet the transfer threshold
+
+    fn propose_transaction(e: Env, proposer: Address, action: ProposalAction) -> u32 {
+        create_proposal(&e, proposer, action)
+    }
+
+    fn approve_proposal(e: Env, signer: Address, proposal_id: u32) {
+        approve_proposal(&e, signer, proposal_id)
+    }
+
+    fn execute_proposal(e: Env, proposal_id: u32) {
+        execute_proposal(&e, proposal_id)
+    }
+
+    fn revoke_proposal(e: Env, proposer: Address, proposal_id: u32) {
+        revoke_proposal(&e, proposer, proposal_id)
+    }
+
+    fn get_proposal(e: Env, proposal_id: u32) -> Option<Proposal> {
+        read_proposal(&e, proposal_id)
+    }
+
+    fn get_proposal_count(e: Env) -> u32 {
+        read_proposal_count(&e)
+    }
+
+    fn get_proposals(e: Env, status: ProposalStatus, start: u32, limit: u32) -> Vec<Proposal> {
+        read_proposals(&e, status, start, limit)
+    }
+
+    fn get_credential_epoch(e: Env) -> u32 {
+        read_credential_epoch(&e)
+    }
+
+    //Failed passkey verifications of the account, so the owner can spot brute force attempts
+    fn get_failed_attempts(e: Env) -> FailedAttempts {
+        read_failed_attempts(&e)
+    }
+
+    fn get_executor_failed_attempts(e: Env, executor: Address) -> FailedAttempts {
+        read_executor_failed_attempts(&e, &executor)
+    }
+
+    fn get_linked_profiles(e: Env) -> Vec<LinkedProfile> {
+        read_linked_profiles(&e)
+    }
+
     //Gets encrypted keys needed for validation
-    fn get_encrypted_keys(e: Env, profile_id: String) -> EncryptedKeys {
-        read_encrypted_keys(&e, profile_id)
+    fn get_encrypted_keys(e: Env, platform: Platform, profile_id: String) -> EncryptedKeys {
+        read_encrypted_keys(&e, platform, profile_id)
+    }
+
+    //Gets the platform the account was created with, Stellar for accounts created with an address
+    fn get_creation_platform(e: Env) -> Platform {
+        read_creation_platform(&e)
     }
 
     //Gets the executor selected
     fn get_executor(e: Env, index: u32) -> Address {
         read_executor(&e, index)
     }
+
     //get all tokens with balance greater than zeor
 
     fn get_tokens(e: Env) -> Vec<Token> {
         read_tokens(&e)
     }
 
-    //get the balance of a specific token
+    //get the spendable balance of a specific token, reserved stream and vault funds are left out
 
     fn get_balance(e: Env, token_id: Address) -> i128 {
-        read_balance(&e, token_id)
+        read_spendable(&e, token_id)
     }
 
-    //Get transaction nonce
-    fn get_nonce(e: Env) -> u32 {
-        read_nonce(&e)
+    //Decimals, symbol and name of a token, so amounts can be shown in whole units
+    fn get_token_metadata(e: Env, token_id: Address) -> TokenMetadata {
+        read_token_metadata(&e, token_id)
+    }
+
+    //Address of the XLM Stellar Asset Contract, sending it moves native lumens
+    fn get_native_token(e: Env) -> Address {
+        native_token(&e)
+    }
+
+    //Get the nonce the next passkey authorized call of a linked profile must use
+    fn get_nonce(e: Env, platform: Platform, profile_id: String) -> u32 {
+        read_nonce(&e, platform, profile_id)
     }
 
     fn get_tx_nonce(e: Env) -> Bytes {
@@ -434,13 +1450,186 @@ impl SubAccountTrait for SubAccount {
         read_quest_data(&e)
     }
 
+    fn get_quest_rule(e: Env, action: QuestAction) -> QuestRule {
+        read_quest_rule(&e, action)
+    }
+
+    //The controller publishes the rewards quest points can be redeemed for
+
+    fn set_reward_offer(e: Env, offer_id: u32, offer: RewardOffer) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        write_reward_offer(&e, offer_id, offer);
+    }
+
+    fn remove_reward_offer(e: Env, offer_id: u32) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        remove_reward_offer(&e, offer_id);
+    }
+
+    //Redeem quest points for a reward offer, authorized by the owner external account
+
+    fn redeem_points_addr(e: Env, signers: Vec<Address>, offer_id: u32) {
+        require_signers(&e, SignerCategory::Transfer, signers);
+        redeem_offer(&e, offer_id);
+    }
+
+    //Redeem quest points for a reward offer, the owner authenticate using social credentials
+
+    fn redeem_points_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        offer_id: u32,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "redeem_points_pkey", offer_id, || {
+            redeem_offer(&e, offer_id)
+        })
+        .is_some()
+    }
+
+    fn get_reward_offer(e: Env, offer_id: u32) -> Option<RewardOffer> {
+        read_reward_offer(&e, offer_id)
+    }
+
+    fn get_redemption_count(e: Env) -> u32 {
+        read_redemption_count(&e)
+    }
+
+    fn get_redemptions(e: Env, start: u32, limit: u32) -> Vec<Redemption> {
+        read_redemptions(&e, start, limit)
+    }
+
     fn get_allowance(e: Env) -> i128 {
         read_max_allowance(&e)
     }
 
-    fn upgrade(e: Env, new_wasm_hash: BytesN<32>) {
-        let owner = read_owner(&e).unwrap();
-        owner.require_auth();
-        e.deployer().update_current_contract_wasm(new_wasm_hash);
+    //Sets the token and amount the account pays executors for each passkey action they submit
+    //and the daily cap, None stops the reimbursements
+
+    fn set_fee_config_addr(e: Env, signers: Vec<Address>, config: Option<FeeConfig>) {
+        require_signers(&e, SignerCategory::Config, signers);
+        write_fee_config(&e, config);
+    }
+
+    fn set_fee_config_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        config: Option<FeeConfig>,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "set_fee_config_pkey", config.clone(), || {
+            write_fee_config(&e, config)
+        })
+        .is_some()
+    }
+
+    fn get_fee_config(e: Env) -> Option<FeeConfig> {
+        read_fee_config(&e)
+    }
+
+    //Fees paid to executors during the current day
+    fn get_fee_spending(e: Env) -> FeeSpending {
+        read_fee_spending(&e)
+    }
+
+    //The controller publishes the wasm hashes of audited sub-account code
+    //Upgrades are only allowed to a hash on this list
+
+    fn set_wasm_approved(e: Env, wasm_hash: BytesN<32>, approved: bool) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        write_wasm_approved(&e, wasm_hash, approved);
+    }
+
+    fn is_wasm_approved(e: Env, wasm_hash: BytesN<32>) -> bool {
+        read_is_wasm_approved(&e, wasm_hash)
+    }
+
+    //Upgrade by the signers, the listed signers must meet the upgrade threshold
+    fn upgrade(e: Env, signers: Vec<Address>, new_wasm_hash: BytesN<32>) {
+        require_signers(&e, SignerCategory::Upgrade, signers);
+        upgrade_contract(&e, new_wasm_hash);
+    }
+
+    //Upgrade for accounts created with social credentials, the owner authenticate using
+    //the passkey and only authorized executors can sign the invocation
+    fn upgrade_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+        new_wasm_hash: BytesN<32>,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "upgrade_pkey", new_wasm_hash.clone(), || {
+            upgrade_contract(&e, new_wasm_hash)
+        })
+        .is_some()
+    }
+
+    //The controller can schedule an upgrade, it can only be executed after the timelock
+    //giving the owner time to cancel it
+    fn propose_upgrade(e: Env, new_wasm_hash: BytesN<32>) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        if !read_is_wasm_approved(&e, new_wasm_hash.clone()) {
+            panic!("Wasm hash has not been approved by the controller")
+        }
+        let pending = PendingUpgrade {
+            wasm_hash: new_wasm_hash,
+            unlock_ledger: e.ledger().sequence() + UPGRADE_TIMELOCK,
+        };
+        write_pending_upgrade(&e, pending);
+    }
+
+    fn execute_upgrade(e: Env) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        let pending = read_pending_upgrade(&e).expect("No pending upgrade");
+        if e.ledger().sequence() < pending.unlock_ledger {
+            panic!("Upgrade timelock has not expired")
+        }
+        upgrade_contract(&e, pending.wasm_hash);
+    }
+
+    fn cancel_upgrade_addr(e: Env, signers: Vec<Address>) {
+        require_signers(&e, SignerCategory::Upgrade, signers);
+        remove_pending_upgrade(&e);
+    }
+
+    fn cancel_upgrade_pkey(
+        e: Env,
+        executor_index: u32,
+        nonce: u32,
+        expiration_ledger: u32,
+        passkey_proof: BytesN<65>,
+    ) -> bool {
+        let auth = PkeyAuth::new(executor_index, nonce, expiration_ledger, passkey_proof);
+        run_pkey(&e, auth, "cancel_upgrade_pkey", (), || {
+            remove_pending_upgrade(&e)
+        })
+        .is_some()
+    }
+
+    fn get_pending_upgrade(e: Env) -> Option<PendingUpgrade> {
+        read_pending_upgrade(&e)
+    }
+
+    //Moves the profile of an account deployed before linked profiles to the current keys
+    //the controller registers the public key of the profile passkey and maps its platform
+
+    fn migrate_profile(e: Env, platform: Platform, passkey: BytesN<65>) {
+        let controller = read_controller(&e);
+        controller.require_auth();
+        migrate_legacy_profile(&e, platform, passkey);
     }
 }
@@ -0,0 +1,133 @@
+use soroban_sdk::{token, Address, Env, Vec};
+
+use crate::{
+    balance::{read_unreserved, write_accounted, write_balance},
+    data::{DataKey, SpenderApproval, MAX_SPENDER_APPROVALS},
+    events::{spender_approved, spenders_revoked},
+};
+
+//Approvals granted by the sub-account, expired ones are dropped on the next approval
+pub fn read_spender_approvals(e: &Env) -> Vec<SpenderApproval> {
+    let key = DataKey::SpenderApprovals;
+    e.storage().instance().get(&key).unwrap_or(Vec::new(e))
+}
+
+fn write_spender_approvals(e: &Env, approvals: &Vec<SpenderApproval>) {
+    let key = DataKey::SpenderApprovals;
+    e.storage().instance().set(&key, approvals);
+}
+
+//Part of the approval the spender already pulled with transfer_from, the token allowance goes down
+//with every pull and reads zero once the approval has expired, so the pulls of an expired
+//approval cannot be told apart from its unused part and are not counted
+fn read_pulled(e: &Env, approval: &SpenderApproval) -> i128 {
+    if approval.expiration_ledger < e.ledger().sequence() {
+        return 0;
+    }
+    let token = token::Client::new(e, &approval.token_id);
+    approval.amount - token.allowance(&e.current_contract_address(), &approval.spender)
+}
+
+//Pulls of the token not recorded yet, for the getters that cannot record them
+pub fn read_unrecorded_pulls(e: &Env, token_id: &Address) -> i128 {
+    read_spender_approvals(e)
+        .iter()
+        .filter(|approval| approval.token_id == *token_id)
+        .map(|approval| read_pulled(e, &approval))
+        .sum()
+}
+
+//Spender pulls never go through the sub-account, they are taken out of the balances once
+//seen and the approvals are brought down to what is left of them
+pub fn record_spender_pulls(e: &Env) {
+    let mut approvals = Vec::new(e);
+    for mut approval in read_spender_approvals(e).iter() {
+        let pulled = read_pulled(e, &approval);
+        if pulled > 0 {
+            write_balance(e, approval.token_id.clone(), -pulled);
+            write_accounted(e, approval.token_id.clone(), -pulled);
+            approval.amount -= pulled;
+        }
+        if approval.amount > 0 {
+            approvals.push_back(approval);
+        }
+    }
+    write_spender_approvals(e, &approvals);
+}
+
+//What the tracked spenders of the token can still pull, the token allowance goes down with every
+//pull and reads zero once the approval has expired, except leaves out the spender a new
+//approval replaces
+pub fn read_approved_total(e: &Env, token_id: &Address, except: Option<&Address>) -> i128 {
+    let token = token::Client::new(e, token_id);
+    let contract_address = e.current_contract_address();
+    read_spender_approvals(e)
+        .iter()
+        .filter(|approval| approval.token_id == *token_id && Some(&approval.spender) != except)
+        .map(|approval| token.allowance(&contract_address, &approval.spender))
+        .sum()
+}
+
+//Calls the token approve as the sub-account, an amount of zero removes the approval
+//the approvals of a token cannot add up to more than its unreserved balance
+pub fn approve_spender(
+    e: &Env,
+    token_id: Address,
+    spender: Address,
+    amount: i128,
+    expiration_ledger: u32,
+) {
+    if amount < 0 {
+        panic!("Approval amount cannot be negative")
+    }
+    record_spender_pulls(e);
+    if amount > 0
+        && read_approved_total(e, &token_id, Some(&spender)) + amount
+            > read_unreserved(e, token_id.clone())
+    {
+        panic!("Approvals exceed the unreserved balance")
+    }
+    let token = token::Client::new(e, &token_id);
+    token.approve(
+        &e.current_contract_address(),
+        &spender,
+        &amount,
+        &expiration_ledger,
+    );
+
+    let ledger = e.ledger().sequence();
+    let mut approvals = Vec::new(e);
+    for approval in read_spender_approvals(e).iter() {
+        let replaced = approval.token_id == token_id && approval.spender == spender;
+        if !replaced && approval.expiration_ledger >= ledger {
+            approvals.push_back(approval);
+        }
+    }
+    if amount > 0 {
+        approvals.push_back(SpenderApproval {
+            token_id: token_id.clone(),
+            spender: spender.clone(),
+            amount,
+            expiration_ledger,
+        });
+    }
+    if approvals.len() > MAX_SPENDER_APPROVALS {
+        panic!("Maximum number of spender approvals reached")
+    }
+    write_spender_approvals(e, &approvals);
+    spender_approved(e, spender, token_id, amount, expiration_ledger);
+}
+
+//Sets every tracked approval back to zero
+pub fn revoke_all_spenders(e: &Env) {
+    record_spender_pulls(e);
+    let approvals = read_spender_approvals(e);
+    let contract_address = e.current_contract_address();
+    let ledger = e.ledger().sequence();
+    for approval in approvals.iter() {
+        let token = token::Client::new(e, &approval.token_id);
+        token.approve(&contract_address, &approval.spender, &0, &ledger);
+    }
+    write_spender_approvals(e, &Vec::new(e));
+    spenders_revoked(e, approvals.len());
+}
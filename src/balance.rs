@@ -1,5 +1,9 @@
-use crate::data::{DataKey, BUMP_AMOUNT, LIFETIME_THRESHOLD};
-use soroban_sdk::{Address, Env};
+use crate::{
+    approvals::{read_approved_total, read_unrecorded_pulls},
+    data::{BalanceKey, DataKey, BUMP_AMOUNT, LIFETIME_THRESHOLD},
+    tokens::add_token,
+};
+use soroban_sdk::{token, Address, Env};
 
 pub fn read_balance(e: &Env, token_id: Address) -> i128 {
     let key = DataKey::Balance(token_id);
@@ -22,3 +26,80 @@ pub fn write_balance(e: &Env, token_id: Address, amount: i128) {
         .persistent()
         .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
 }
+
+//Tracked balance less the spender pulls not recorded yet, so the getters never report funds a
+//spender already took
+pub fn read_current_balance(e: &Env, token_id: Address) -> i128 {
+    read_balance(e, token_id.clone()) - read_unrecorded_pulls(e, &token_id)
+}
+
+//Part of the balance the account can spend, the funds reserved for streams and vaults are locked
+pub fn read_spendable(e: &Env, token_id: Address) -> i128 {
+    read_current_balance(e, token_id.clone()) - read_reserved(e, token_id)
+}
+
+//Accounts upgraded from before the accounted balance start from their tracked balance
+pub fn read_accounted(e: &Env, token_id: Address) -> i128 {
+    let key = BalanceKey::Accounted(token_id.clone());
+    if let Some(accounted) = e.storage().persistent().get::<BalanceKey, i128>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        accounted
+    } else {
+        read_balance(e, token_id)
+    }
+}
+
+//A positive amount came in, a negative amount went out
+pub fn write_accounted(e: &Env, token_id: Address, amount: i128) {
+    let key = BalanceKey::Accounted(token_id.clone());
+    let accounted = read_accounted(e, token_id) + amount;
+    e.storage().persistent().set(&key, &accounted);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn read_token_balance(e: &Env, token_id: &Address) -> i128 {
+    let token = token::Client::new(e, token_id);
+    token.balance(&e.current_contract_address())
+}
+
+//Part of the token balance set aside, for example for streams, that sends cannot spend
+pub fn read_reserved(e: &Env, token_id: Address) -> i128 {
+    let key = DataKey::Reserved(token_id);
+    if let Some(reserved) = e.storage().persistent().get::<DataKey, i128>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        reserved
+    } else {
+        0
+    }
+}
+
+//A positive amount reserves funds, a negative amount releases them
+//a token with reserved funds is registered so contract calls are checked against them
+pub fn write_reserved(e: &Env, token_id: Address, amount: i128) {
+    if amount > 0 {
+        add_token(e, token_id.clone());
+    }
+    let key = DataKey::Reserved(token_id.clone());
+    let reserved = read_reserved(e, token_id) + amount;
+    e.storage().persistent().set(&key, &reserved);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+//Token balance held by the account that is not reserved
+pub fn read_unreserved(e: &Env, token_id: Address) -> i128 {
+    read_token_balance(e, &token_id) - read_reserved(e, token_id)
+}
+
+//What the account can still spend or reserve, the approved spenders can pull their part of the
+//unreserved balance at any time so it is left out too
+pub fn read_available(e: &Env, token_id: Address) -> i128 {
+    read_unreserved(e, token_id.clone()) - read_approved_total(e, &token_id, None)
+}
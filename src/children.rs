@@ -0,0 +1,136 @@
+use soroban_sdk::{Address, Env, Map, Vec};
+
+use crate::{
+    balance::write_balance,
+    data::{AuthPath, ChildSlot, DataKey, Memo, BUMP_AMOUNT, LIFETIME_THRESHOLD, MAX_CHILD_SLOTS},
+    events::{child_created, child_revoked, child_spent},
+    transact::send_token,
+};
+
+pub fn read_child_count(e: &Env) -> u32 {
+    let key = DataKey::ChildSlotCount;
+    if let Some(count) = e.storage().persistent().get::<DataKey, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        count
+    } else {
+        0
+    }
+}
+
+fn write_child_count(e: &Env, count: u32) {
+    let key = DataKey::ChildSlotCount;
+    e.storage().persistent().set(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn read_child(e: &Env, child_id: u32) -> Option<ChildSlot> {
+    let key = DataKey::ChildSlot(child_id);
+    e.storage().persistent().get(&key)
+}
+
+fn write_child(e: &Env, child: &ChildSlot) {
+    let key = DataKey::ChildSlot(child.id);
+    e.storage().persistent().set(&key, child);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+//Every child slot, including the expired and revoked ones that were not reused yet
+pub fn read_children(e: &Env) -> Vec<ChildSlot> {
+    let mut children = Vec::new(e);
+    for child_id in 1..=read_child_count(e) {
+        if let Some(child) = read_child(e, child_id) {
+            children.push_back(child);
+        }
+    }
+    children
+}
+
+//Id of the new slot, once MAX_CHILD_SLOTS slots exist the first revoked or expired one is reused
+fn next_child_id(e: &Env) -> u32 {
+    let count = read_child_count(e);
+    if count < MAX_CHILD_SLOTS {
+        write_child_count(e, count + 1);
+        return count + 1;
+    }
+    let ledger = e.ledger().sequence();
+    read_children(e)
+        .iter()
+        .find(|child| child.revoked || ledger > child.expiration_ledger)
+        .map(|child| child.id)
+        .expect("Maximum number of child slots reached")
+}
+
+pub fn create_child(
+    e: &Env,
+    signer: Address,
+    budgets: Map<Address, i128>,
+    expiration_ledger: u32,
+) -> u32 {
+    if budgets.values().iter().any(|budget| budget < 0) {
+        panic!("Child budget cannot be negative")
+    }
+    if expiration_ledger <= e.ledger().sequence() {
+        panic!("Child slot expiration must be in the future")
+    }
+    let child_id = next_child_id(e);
+    let child = ChildSlot {
+        id: child_id,
+        signer: signer.clone(),
+        budgets,
+        spent: Map::new(e),
+        expiration_ledger,
+        revoked: false,
+    };
+    write_child(e, &child);
+    child_created(e, child_id, signer, expiration_ledger);
+    child_id
+}
+
+//The child signer sends from the parent balance within what is left of the token budget
+pub fn child_send(
+    e: &Env,
+    child_id: u32,
+    to: Address,
+    token_id: Address,
+    amount: i128,
+    memo: Memo,
+) {
+    let mut child = read_child(e, child_id).expect("Child slot not found");
+    child.signer.require_auth();
+    if child.revoked {
+        panic!("Child slot has been revoked")
+    }
+    if e.ledger().sequence() > child.expiration_ledger {
+        panic!("Child slot has expired")
+    }
+    if amount <= 0 {
+        panic!("Amount must be greater than zero")
+    }
+    let budget = child.budgets.get(token_id.clone()).unwrap_or(0);
+    let spent = child.spent.get(token_id.clone()).unwrap_or(0) + amount;
+    if spent > budget {
+        panic!("Amount exceeds the child budget")
+    }
+    child.spent.set(token_id.clone(), spent);
+    write_child(e, &child);
+
+    send_token(e, &to, &token_id, amount, AuthPath::Child, memo);
+    write_balance(e, token_id.clone(), -amount);
+    child_spent(e, child_id, token_id, amount, spent);
+}
+
+pub fn revoke_child(e: &Env, child_id: u32) {
+    let mut child = read_child(e, child_id).expect("Child slot not found");
+    if child.revoked {
+        panic!("Child slot has already been revoked")
+    }
+    child.revoked = true;
+    write_child(e, &child);
+    child_revoked(e, child_id);
+}
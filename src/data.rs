@@ -1,13 +1,31 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Symbol, Val, Vec};
+
+use crate::types::Platform;
 
 pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
 pub(crate) const BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 pub(crate) const LIFETIME_THRESHOLD: u32 = BUMP_AMOUNT - DAY_IN_LEDGERS;
+pub(crate) const UPGRADE_TIMELOCK: u32 = 7 * DAY_IN_LEDGERS;
+pub(crate) const MAX_LINKED_PROFILES: u32 = 5;
+pub(crate) const MAX_SIGNERS: u32 = 10;
+pub(crate) const OWNER_TRANSFER_EXPIRY: u32 = 7 * DAY_IN_LEDGERS;
+pub(crate) const PROPOSAL_EXPIRY: u32 = 7 * DAY_IN_LEDGERS;
+pub(crate) const MAX_TRANSACTION_HISTORY: u32 = 100;
+pub(crate) const MAX_MEMO_LENGTH: u32 = 64;
+pub(crate) const MAX_SPENDER_APPROVALS: u32 = 20;
+pub(crate) const MAX_VAULT_NAME_LENGTH: u32 = 32;
+pub(crate) const MAX_CHILD_SLOTS: u32 = 20;
+pub(crate) const MAX_BENEFICIARIES: u32 = 10;
+pub(crate) const TOTAL_SHARE_BPS: u32 = 10_000;
+pub(crate) const MIN_INACTIVITY_DAYS: u32 = 30;
+pub(crate) const AUTH_FAILURE_THRESHOLD: u32 = 3;
+pub(crate) const AUTH_LOCKOUT_BASE: u32 = 60;
+pub(crate) const AUTH_LOCKOUT_MAX: u32 = DAY_IN_LEDGERS;
 
 #[derive(Clone)]
 #[contracttype]
 pub struct ProfileEncrypted {
-    pub platform: String,
+    pub platform: Platform,
     pub profile_id: String,
     pub gen_salt_encrypted: String,
     pub salt_encryption_iv: String,
@@ -15,6 +33,14 @@ pub struct ProfileEncrypted {
     pub index_encryption_iv: String,
 }
 
+//Social profile linked to the account, each has its own encrypted keys and passkey
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LinkedProfile {
+    pub platform: Platform,
+    pub profile_id: String,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct EncryptedKeys {
@@ -24,28 +50,329 @@ pub struct EncryptedKeys {
     pub index_encryption_iv: String,
 }
 
+//decimals lets clients normalize the raw amounts used in balances, allowances and events
+//balance is the spendable part, the funds reserved for streams and vaults are left out
 #[derive(Clone)]
 #[contracttype]
 pub struct Token {
     pub token_id: Address,
     pub balance: i128,
     pub smart_transact: bool,
+    pub decimals: u32,
+    pub symbol: String,
+    pub name: String,
+    pub is_native: bool,
+}
+
+//Cached when the token is first registered, token metadata does not change
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenMetadata {
+    pub decimals: u32,
+    pub symbol: String,
+    pub name: String,
 }
 
 #[derive(Clone)]
 #[contracttype]
-pub enum DataKey {
+pub struct PendingUpgrade {
+    pub wasm_hash: BytesN<32>,
+    pub unlock_ledger: u32,
+}
+
+//Account signer, a call authorized by several signers sums their weights
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Signer {
+    pub address: Address,
+    pub weight: u32,
+}
+
+//Each category has its own signer threshold
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum SignerCategory {
+    Transfer,
+    Config,
+    Upgrade,
+}
+
+//Owner transfer proposed by the current owner, the new owner must accept it before expiration_ledger
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PendingOwner {
+    pub new_owner: Address,
+    pub expiration_ledger: u32,
+}
+
+//Transaction a signer proposes from the sub-account
+//Transfer is (to, token_id, amount), Invoke is (contract, function, args)
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ProposalAction {
+    Transfer(Address, Address, i128),
+    Invoke(Address, Symbol, Vec<Val>),
+}
+
+//Expired is never stored, a pending proposal past its expiration_ledger is reported as expired
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Revoked,
+    Expired,
+}
+
+//Proposal waiting for signer approvals, it can be executed once the approving signers
+//meet the transfer threshold
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub action: ProposalAction,
+    pub approvals: Vec<Address>,
+    pub expiration_ledger: u32,
+    pub status: ProposalStatus,
+}
+
+//Fee the sub-account pays the executor in token_id for every successful passkey action
+//at most daily_cap is paid per day, counted in DAY_IN_LEDGERS periods
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeConfig {
+    pub token_id: Address,
+    pub fee_per_action: i128,
+    pub daily_cap: i128,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeSpending {
+    pub day: u32,
+    pub spent: i128,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TransactionDirection {
+    Received,
+    Sent,
+}
+
+//How the transfer was authorized, Depositor is an external account sending funds in
+//Stream is a withdrawal from a stream the owner created, Child is a spend from a child slot
+//Inheritance is a payout to a beneficiary of an inactive account, Fee is the reimbursement of
+//the executor that submitted a passkey action
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum AuthPath {
     Owner,
-    PassKeyHash,
-    ProfileKeys,
+    Pkey,
+    Controller,
+    Depositor,
+    Stream,
+    Child,
+    Inheritance,
+    Fee,
+}
+
+//Payment metadata for reconciliation, a text of at most MAX_MEMO_LENGTH bytes or a 32 byte reference
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Memo {
+    None,
+    Text(String),
+    Hash(BytesN<32>),
+}
+
+//Token transfer in or out of the sub-account, id is the transaction number
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransactionRecord {
+    pub id: u32,
+    pub direction: TransactionDirection,
+    pub token_id: Address,
+    pub counterparty: Address,
+    pub amount: i128,
+    pub ledger: u32,
+    pub auth: AuthPath,
+    pub memo: Memo,
+}
+
+//Token allowance the sub-account granted to a third party spender
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SpenderApproval {
+    pub token_id: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+//Terms of a stream, rate_per_ledger accrues from start_ledger to end_ledger
+//nothing can be withdrawn before cliff_ledger
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct StreamTerms {
+    pub recipient: Address,
+    pub token_id: Address,
+    pub rate_per_ledger: i128,
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    pub cliff_ledger: u32,
+}
+
+//Stream paying the recipient from the sub-account, the unwithdrawn amount stays reserved
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Stream {
+    pub id: u32,
+    pub terms: StreamTerms,
+    pub withdrawn: i128,
+    pub cancelled: bool,
+}
+
+//Named savings vault, its balance is taken out of the spendable balance of the token and
+//reserved until the owner withdraws it after lock_until_ledger
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Vault {
+    pub id: u32,
+    pub name: String,
+    pub token_id: Address,
+    pub balance: i128,
+    pub goal: i128,
+    pub lock_until_ledger: u32,
+}
+
+//Spending slot for a member, the signer spends from the parent balance up to the budget
+//of each token until expiration_ledger or until the parent revokes it
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ChildSlot {
+    pub id: u32,
+    pub signer: Address,
+    pub budgets: Map<Address, i128>,
+    pub spent: Map<Address, i128>,
+    pub expiration_ledger: u32,
+    pub revoked: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Beneficiary {
+    pub address: Address,
+    pub share_bps: u32,
+}
+
+//Beneficiaries can claim the funds once the account has had no owner or pkey activity
+//for inactivity_days plus grace_days, shares are in basis points
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct InheritanceConfig {
+    pub beneficiaries: Vec<Beneficiary>,
+    pub inactivity_days: u32,
+    pub grace_days: u32,
+}
+
+//Expired is never stored, an open request past its expiration_ledger is reported as expired
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PaymentRequestStatus {
+    Open,
+    Paid,
+    Cancelled,
+    Expired,
+}
+
+//Payment request issued by the sub-account, any payer can settle it once before it expires
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PaymentRequest {
+    pub id: u32,
+    pub token_id: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+    pub memo: Memo,
+    pub status: PaymentRequestStatus,
+    pub payer: Option<Address>,
+}
+
+//Activities that can earn quest points
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum QuestAction {
+    Init,
+    SetAllowance,
+    SetSigner,
+    Receive,
+    Send,
+}
+
+//Reward configured by the controller for an action
+//min_amount only applies to receive and send, cooldown is in ledgers between awards
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct QuestRule {
+    pub points: u32,
+    pub repeatable: bool,
+    pub min_amount: i128,
+    pub cooldown: u32,
+}
+
+//Reward published by the controller that can be bought with quest points
+//the controller must approve the sub-account to pull the reward amount of token_id
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RewardOffer {
+    pub cost: u32,
+    pub token_id: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Redemption {
+    pub offer_id: u32,
+    pub cost: u32,
+    pub token_id: Address,
+    pub amount: i128,
+    pub ledger: u32,
+}
+
+//Failed passkey verifications, count is reset by the next successful one
+//once count reaches the threshold, passkey calls are locked until locked_until
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct FailedAttempts {
+    pub count: u32,
+    pub total: u32,
+    pub last_failure_ledger: u32,
+    pub locked_until: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Signers,
+    SignerThreshold(SignerCategory),
+    PassKey(Platform, String),
+    ProfileKeys(Platform, String),
+    CreationPlatform,
+    CredentialEpoch,
+    LinkedProfiles,
     Controller,
     MaxAllowance,
     Balance(Address),
     TokenIds(u32),
     SmartTransactActive(Address),
-    Nonce,
+    Nonce(Platform, String),
     TxNonce,
     TokenAdded(Address),
+    TokenMetadata(Address),
     TokensCount,
     // UserPlatformId(u32),
     Initialized,
@@ -55,10 +382,70 @@ pub enum DataKey {
     ExecutorsSet,
     QuestPoints,
     TransactionCount,
+    ApprovedWasm(BytesN<32>),
+    PendingUpgrade,
+    QuestRule(QuestAction),
+    QuestLastAward(QuestAction),
+    RewardOffer(u32),
+    Redemption(u32),
+    RedemptionCount,
+    FailedAttempts,
+    ExecutorFailedAttempts(Address),
+    PendingOwner,
+    Proposal(u32),
+    ProposalCount,
+    FeeConfig,
+    FeeSpending,
+    Transaction(u32),
+    SpenderApprovals,
+    Reserved(Address),
+    Stream(u32),
+    StreamCount,
+    Vault(u32),
+    VaultCount,
+    ChildSlot(u32),
+    ChildSlotCount,
+    Inheritance,
+    LastActivity,
+}
+
+//Storage keys of payment requests, kept apart as DataKey is at the 50 variant limit of
+//contract types
+#[derive(Clone)]
+#[contracttype]
+pub enum RequestKey {
+    Request(u32),
+    Count,
+}
+
+//Token balance the sub-account has accounted for, moved by its own transfers and by the
+//spender pulls it records, it is what credit_deposit compares the token balance with
+//kept apart from DataKey::Balance that executors can adjust for the dashboard
+#[derive(Clone)]
+#[contracttype]
+pub enum BalanceKey {
+    Accounted(Address),
+}
+
+//Storage keys of accounts deployed before weighted signers and linked profiles, they are
+//only read to move their values to the current keys and removed once moved
+#[derive(Clone)]
+#[contracttype]
+pub enum LegacyKey {
+    Owner,
+    PassKeyHash,
+    ProfileKeys,
+    Nonce,
 }
 
-//Stellar account pubkey: 0
-// Email: 1
-// X (Twitter): 2
-// Discord: 3
-// Telegram: 4
+//Profile of an account deployed before linked profiles, the platform was a free form string
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyProfile {
+    pub platform: String,
+    pub profile_id: String,
+    pub gen_salt_encrypted: String,
+    pub salt_encryption_iv: String,
+    pub index_encrypted: String,
+    pub index_encryption_iv: String,
+}
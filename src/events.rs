@@ -0,0 +1,207 @@
+use soroban_sdk::{symbol_short, Address, Env, String};
+
+use crate::{
+    data::{Memo, QuestAction, SignerCategory, TransactionDirection},
+    types::Platform,
+};
+
+pub fn quest_points_awarded(e: &Env, action: QuestAction, points: u32, total: u32) {
+    let topics = (symbol_short!("quest"), action);
+    e.events().publish(topics, (points, total));
+}
+
+pub fn points_redeemed(e: &Env, offer_id: u32, cost: u32, remaining: u32) {
+    let topics = (symbol_short!("quest"), symbol_short!("redeem"), offer_id);
+    e.events().publish(topics, (cost, remaining));
+}
+
+pub fn credentials_rotated(e: &Env, platform: Platform, profile_id: String, epoch: u32) {
+    let topics = (symbol_short!("cred"), symbol_short!("rotated"), platform);
+    e.events().publish(topics, (profile_id, epoch));
+}
+
+pub fn owner_proposed(e: &Env, new_owner: Address, expiration_ledger: u32) {
+    let topics = (symbol_short!("owner"), symbol_short!("proposed"));
+    e.events().publish(topics, (new_owner, expiration_ledger));
+}
+
+pub fn owner_transferred(e: &Env, new_owner: Address) {
+    let topics = (symbol_short!("owner"), symbol_short!("accepted"));
+    e.events().publish(topics, new_owner);
+}
+
+pub fn owner_transfer_cancelled(e: &Env, new_owner: Address) {
+    let topics = (symbol_short!("owner"), symbol_short!("cancelled"));
+    e.events().publish(topics, new_owner);
+}
+
+pub fn signer_updated(e: &Env, address: Address, weight: u32) {
+    let topics = (symbol_short!("signer"), symbol_short!("weight"), address);
+    e.events().publish(topics, weight);
+}
+
+pub fn threshold_updated(e: &Env, category: SignerCategory, threshold: u32) {
+    let topics = (
+        symbol_short!("signer"),
+        symbol_short!("threshold"),
+        category,
+    );
+    e.events().publish(topics, threshold);
+}
+
+pub fn pkey_auth_failed(e: &Env, executor: Address, count: u32, locked_until: u32) {
+    let topics = (symbol_short!("auth"), symbol_short!("failed"), executor);
+    e.events().publish(topics, (count, locked_until));
+}
+
+pub fn proposal_created(e: &Env, proposal_id: u32, proposer: Address, expiration_ledger: u32) {
+    let topics = (
+        symbol_short!("proposal"),
+        symbol_short!("created"),
+        proposal_id,
+    );
+    e.events().publish(topics, (proposer, expiration_ledger));
+}
+
+pub fn proposal_approved(e: &Env, proposal_id: u32, signer: Address) {
+    let topics = (
+        symbol_short!("proposal"),
+        symbol_short!("approved"),
+        proposal_id,
+    );
+    e.events().publish(topics, signer);
+}
+
+pub fn proposal_revoked(e: &Env, proposal_id: u32) {
+    let topics = (
+        symbol_short!("proposal"),
+        symbol_short!("revoked"),
+        proposal_id,
+    );
+    e.events().publish(topics, ());
+}
+
+pub fn proposal_executed(e: &Env, proposal_id: u32) {
+    let topics = (
+        symbol_short!("proposal"),
+        symbol_short!("executed"),
+        proposal_id,
+    );
+    e.events().publish(topics, ());
+}
+
+pub fn fee_reimbursed(e: &Env, executor: Address, token_id: Address, amount: i128, spent: i128) {
+    let topics = (symbol_short!("fee"), symbol_short!("paid"), executor);
+    e.events().publish(topics, (token_id, amount, spent));
+}
+
+pub fn transaction_recorded(
+    e: &Env,
+    direction: TransactionDirection,
+    counterparty: Address,
+    id: u32,
+    token_id: Address,
+    amount: i128,
+    memo: Memo,
+) {
+    let topics = (symbol_short!("tx"), direction, counterparty);
+    e.events().publish(topics, (id, token_id, amount, memo));
+}
+
+pub fn spender_approved(
+    e: &Env,
+    spender: Address,
+    token_id: Address,
+    amount: i128,
+    expiration_ledger: u32,
+) {
+    let topics = (symbol_short!("spender"), symbol_short!("approved"), spender);
+    e.events()
+        .publish(topics, (token_id, amount, expiration_ledger));
+}
+
+pub fn spenders_revoked(e: &Env, count: u32) {
+    let topics = (symbol_short!("spender"), symbol_short!("revoked"));
+    e.events().publish(topics, count);
+}
+
+pub fn stream_created(e: &Env, stream_id: u32, recipient: Address, total: i128) {
+    let topics = (symbol_short!("stream"), symbol_short!("created"), stream_id);
+    e.events().publish(topics, (recipient, total));
+}
+
+pub fn stream_withdrawn(e: &Env, stream_id: u32, amount: i128) {
+    let topics = (
+        symbol_short!("stream"),
+        symbol_short!("withdrawn"),
+        stream_id,
+    );
+    e.events().publish(topics, amount);
+}
+
+pub fn stream_cancelled(e: &Env, stream_id: u32, paid: i128, released: i128) {
+    let topics = (
+        symbol_short!("stream"),
+        symbol_short!("cancelled"),
+        stream_id,
+    );
+    e.events().publish(topics, (paid, released));
+}
+
+pub fn vault_created(e: &Env, vault_id: u32, token_id: Address, goal: i128) {
+    let topics = (symbol_short!("vault"), symbol_short!("created"), vault_id);
+    e.events().publish(topics, (token_id, goal));
+}
+
+pub fn vault_deposited(e: &Env, vault_id: u32, amount: i128, balance: i128) {
+    let topics = (symbol_short!("vault"), symbol_short!("deposit"), vault_id);
+    e.events().publish(topics, (amount, balance));
+}
+
+pub fn vault_withdrawn(e: &Env, vault_id: u32, amount: i128, balance: i128) {
+    let topics = (symbol_short!("vault"), symbol_short!("withdrawn"), vault_id);
+    e.events().publish(topics, (amount, balance));
+}
+
+pub fn child_created(e: &Env, child_id: u32, signer: Address, expiration_ledger: u32) {
+    let topics = (symbol_short!("child"), symbol_short!("created"), child_id);
+    e.events().publish(topics, (signer, expiration_ledger));
+}
+
+pub fn child_spent(e: &Env, child_id: u32, token_id: Address, amount: i128, spent: i128) {
+    let topics = (symbol_short!("child"), symbol_short!("spent"), child_id);
+    e.events().publish(topics, (token_id, amount, spent));
+}
+
+pub fn child_revoked(e: &Env, child_id: u32) {
+    let topics = (symbol_short!("child"), symbol_short!("revoked"), child_id);
+    e.events().publish(topics, ());
+}
+
+pub fn inheritance_claimed(e: &Env, claimant: Address, token_id: Address, amount: i128) {
+    let topics = (symbol_short!("inherit"), symbol_short!("claimed"), claimant);
+    e.events().publish(topics, (token_id, amount));
+}
+
+pub fn request_created(e: &Env, request_id: u32, token_id: Address, amount: i128) {
+    let topics = (
+        symbol_short!("request"),
+        symbol_short!("created"),
+        request_id,
+    );
+    e.events().publish(topics, (token_id, amount));
+}
+
+pub fn request_paid(e: &Env, request_id: u32, payer: Address, amount: i128) {
+    let topics = (symbol_short!("request"), symbol_short!("paid"), request_id);
+    e.events().publish(topics, (payer, amount));
+}
+
+pub fn request_cancelled(e: &Env, request_id: u32) {
+    let topics = (
+        symbol_short!("request"),
+        symbol_short!("cancelled"),
+        request_id,
+    );
+    e.events().publish(topics, ());
+}
@@ -0,0 +1,73 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    balance::{read_available, write_balance},
+    data::{AuthPath, DataKey, FeeConfig, FeeSpending, Memo, DAY_IN_LEDGERS},
+    events::fee_reimbursed,
+    transact::send_token,
+};
+
+pub fn read_fee_config(e: &Env) -> Option<FeeConfig> {
+    let key = DataKey::FeeConfig;
+    e.storage().instance().get(&key)
+}
+
+//None stops the sponsored fee reimbursements
+pub fn write_fee_config(e: &Env, config: Option<FeeConfig>) {
+    let key = DataKey::FeeConfig;
+    match config {
+        Some(config) => {
+            if config.fee_per_action < 0 || config.daily_cap < 0 {
+                panic!("Fee amounts cannot be negative")
+            }
+            e.storage().instance().set(&key, &config);
+        }
+        None => e.storage().instance().remove(&key),
+    }
+}
+
+//Spending of the current day, it starts from zero on a new day
+pub fn read_fee_spending(e: &Env) -> FeeSpending {
+    let key = DataKey::FeeSpending;
+    let day = e.ledger().sequence() / DAY_IN_LEDGERS;
+    match e.storage().instance().get::<DataKey, FeeSpending>(&key) {
+        Some(spending) if spending.day == day => spending,
+        _ => FeeSpending { day, spent: 0 },
+    }
+}
+
+fn write_fee_spending(e: &Env, spending: &FeeSpending) {
+    let key = DataKey::FeeSpending;
+    e.storage().instance().set(&key, spending);
+}
+
+//Pays the executor for submitting a passkey action, the fee is reduced to what is left of the
+//daily cap and skipped when the unreserved balance cannot cover it, so the action itself
+//never fails on fees
+pub fn reimburse_fee(e: &Env, executor: &Address) {
+    let Some(config) = read_fee_config(e) else {
+        return;
+    };
+    let mut spending = read_fee_spending(e);
+    let amount = config.fee_per_action.min(config.daily_cap - spending.spent);
+    if amount <= 0 {
+        return;
+    }
+
+    if read_available(e, config.token_id.clone()) < amount {
+        return;
+    }
+    send_token(
+        e,
+        executor,
+        &config.token_id,
+        amount,
+        AuthPath::Fee,
+        Memo::None,
+    );
+    write_balance(e, config.token_id.clone(), -amount);
+
+    spending.spent += amount;
+    write_fee_spending(e, &spending);
+    fee_reimbursed(e, executor.clone(), config.token_id, amount, spending.spent);
+}
@@ -0,0 +1,95 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    balance::{read_available, write_balance},
+    data::{
+        AuthPath, DataKey, InheritanceConfig, Memo, DAY_IN_LEDGERS, MAX_BENEFICIARIES,
+        MIN_INACTIVITY_DAYS, TOTAL_SHARE_BPS,
+    },
+    events::inheritance_claimed,
+    transact::send_token,
+    vaults::release_vaults,
+};
+
+pub fn read_inheritance(e: &Env) -> Option<InheritanceConfig> {
+    let key = DataKey::Inheritance;
+    e.storage().instance().get(&key)
+}
+
+//None removes the beneficiaries, setting them counts as activity and restarts the timer
+pub fn write_inheritance(e: &Env, config: Option<InheritanceConfig>) {
+    let key = DataKey::Inheritance;
+    match config {
+        Some(config) => {
+            if config.beneficiaries.is_empty() || config.beneficiaries.len() > MAX_BENEFICIARIES {
+                panic!("Invalid number of beneficiaries")
+            }
+            let total: u32 = config.beneficiaries.iter().map(|b| b.share_bps).sum();
+            if total != TOTAL_SHARE_BPS {
+                panic!("Beneficiary shares must add up to 10000")
+            }
+            if config.inactivity_days < MIN_INACTIVITY_DAYS {
+                panic!("Inactivity period must be at least 30 days")
+            }
+            e.storage().instance().set(&key, &config);
+        }
+        None => e.storage().instance().remove(&key),
+    }
+    record_activity(e);
+}
+
+pub fn read_last_activity(e: &Env) -> u32 {
+    let key = DataKey::LastActivity;
+    e.storage().instance().get(&key).unwrap_or(0)
+}
+
+//Heartbeat of the account, updated on every owner or pkey authorized action
+pub fn record_activity(e: &Env) {
+    let key = DataKey::LastActivity;
+    e.storage().instance().set(&key, &e.ledger().sequence());
+}
+
+//Any beneficiary can trigger the payout of a token once the account is inactive, the vaults of
+//the token are emptied first, every beneficiary gets their share of the unreserved balance
+//and the last one gets the remainder
+pub fn claim_inheritance(e: &Env, claimant: Address, token_id: Address) -> i128 {
+    claimant.require_auth();
+    let config = read_inheritance(e).expect("No beneficiaries set");
+    if !config.beneficiaries.iter().any(|b| b.address == claimant) {
+        panic!("Address is not a beneficiary")
+    }
+    let inactive_days = config.inactivity_days as u64 + config.grace_days as u64;
+    let claimable_ledger = read_last_activity(e) as u64 + inactive_days * DAY_IN_LEDGERS as u64;
+    if (e.ledger().sequence() as u64) < claimable_ledger {
+        panic!("Account is still active")
+    }
+
+    release_vaults(e, &token_id);
+    let total = read_available(e, token_id.clone());
+    if total <= 0 {
+        panic!("Nothing to claim")
+    }
+    let mut paid = 0;
+    let last = config.beneficiaries.len() - 1;
+    for (index, beneficiary) in config.beneficiaries.iter().enumerate() {
+        let amount = if index as u32 == last {
+            total - paid
+        } else {
+            total * beneficiary.share_bps as i128 / TOTAL_SHARE_BPS as i128
+        };
+        if amount > 0 {
+            send_token(
+                e,
+                &beneficiary.address,
+                &token_id,
+                amount,
+                AuthPath::Inheritance,
+                Memo::None,
+            );
+            paid += amount;
+        }
+    }
+    write_balance(e, token_id.clone(), -total);
+    inheritance_claimed(e, claimant, token_id, total);
+    total
+}
@@ -1,10 +1,28 @@
 #![no_std]
 mod access;
+//pkey entry points and their generated client take the passkey auth on top of their own arguments
+#[allow(clippy::too_many_arguments)]
 mod account;
+mod approvals;
 mod balance;
+mod children;
 mod data;
+mod events;
+mod fees;
+mod inheritance;
+mod lockout;
+mod platform;
+mod proposals;
+mod requests;
+mod rewards;
+mod signers;
+mod streams;
 mod test;
 mod tokens;
 mod transact;
 mod types;
+mod upgrade;
 mod user_quest;
+mod vaults;
+
+pub use crate::account::{SubAccount, SubAccountClient, SubAccountTrait};
@@ -0,0 +1,93 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    data::{DataKey, FailedAttempts, AUTH_FAILURE_THRESHOLD, AUTH_LOCKOUT_BASE, AUTH_LOCKOUT_MAX},
+    events::pkey_auth_failed,
+    inheritance::record_activity,
+};
+
+pub fn read_failed_attempts(e: &Env) -> FailedAttempts {
+    let key = DataKey::FailedAttempts;
+    e.storage().instance().get(&key).unwrap_or_default()
+}
+
+fn write_failed_attempts(e: &Env, attempts: &FailedAttempts) {
+    let key = DataKey::FailedAttempts;
+    e.storage().instance().set(&key, attempts);
+}
+
+pub fn read_executor_failed_attempts(e: &Env, executor: &Address) -> FailedAttempts {
+    let key = DataKey::ExecutorFailedAttempts(executor.clone());
+    e.storage().instance().get(&key).unwrap_or_default()
+}
+
+fn write_executor_failed_attempts(e: &Env, executor: &Address, attempts: &FailedAttempts) {
+    let key = DataKey::ExecutorFailedAttempts(executor.clone());
+    e.storage().instance().set(&key, attempts);
+}
+
+//Lockout doubles with every failure past the threshold, up to one day
+fn lockout_duration(count: u32) -> u32 {
+    if count < AUTH_FAILURE_THRESHOLD {
+        return 0;
+    }
+    let exponent = (count - AUTH_FAILURE_THRESHOLD).min(16);
+    (AUTH_LOCKOUT_BASE << exponent).min(AUTH_LOCKOUT_MAX)
+}
+
+fn add_failure(e: &Env, attempts: &mut FailedAttempts) {
+    let ledger = e.ledger().sequence();
+    attempts.count += 1;
+    attempts.total += 1;
+    attempts.last_failure_ledger = ledger;
+    let lockout = lockout_duration(attempts.count);
+    if lockout > 0 {
+        attempts.locked_until = ledger + lockout;
+    }
+}
+
+fn is_locked(e: &Env, attempts: &FailedAttempts) -> bool {
+    e.ledger().sequence() < attempts.locked_until
+}
+
+//Panics while the account or the executor is locked out
+fn check_lockout(e: &Env, executor: &Address) {
+    if is_locked(e, &read_failed_attempts(e))
+        || is_locked(e, &read_executor_failed_attempts(e, executor))
+    {
+        panic!("Too many failed passkey attempts, try again later")
+    }
+}
+
+//Records the outcome of a passkey verification for the account and the executor
+//a failure is stored and reported to the caller instead of panicking, so it is not rolled back
+pub fn record_pkey_attempt(e: &Env, executor: &Address, authorized: bool) -> bool {
+    check_lockout(e, executor);
+
+    let mut account_attempts = read_failed_attempts(e);
+    let mut executor_attempts = read_executor_failed_attempts(e, executor);
+    if authorized {
+        if account_attempts.count > 0 {
+            account_attempts.count = 0;
+            write_failed_attempts(e, &account_attempts);
+        }
+        if executor_attempts.count > 0 {
+            executor_attempts.count = 0;
+            write_executor_failed_attempts(e, executor, &executor_attempts);
+        }
+        record_activity(e);
+        return true;
+    }
+
+    add_failure(e, &mut account_attempts);
+    add_failure(e, &mut executor_attempts);
+    write_failed_attempts(e, &account_attempts);
+    write_executor_failed_attempts(e, executor, &executor_attempts);
+    pkey_auth_failed(
+        e,
+        executor.clone(),
+        account_attempts.count,
+        account_attempts.locked_until,
+    );
+    false
+}
@@ -0,0 +1,67 @@
+use soroban_sdk::{Env, String};
+
+use crate::{data::DataKey, types::Platform};
+
+const MAX_PROFILE_ID_LEN: usize = 254;
+const STELLAR_ACCOUNT_LEN: usize = 56;
+
+pub fn read_creation_platform(e: &Env) -> Platform {
+    let key = DataKey::CreationPlatform;
+    e.storage().instance().get(&key).unwrap()
+}
+
+pub fn has_creation_platform(e: &Env) -> bool {
+    let key = DataKey::CreationPlatform;
+    e.storage().instance().has(&key)
+}
+
+pub fn write_creation_platform(e: &Env, platform: Platform) {
+    let key = DataKey::CreationPlatform;
+    e.storage().instance().set(&key, &platform);
+}
+
+//Stellar account public key: G followed by 55 base32 characters
+fn is_valid_stellar_account(id: &[u8]) -> bool {
+    id.len() == STELLAR_ACCOUNT_LEN
+        && id[0] == b'G'
+        && id
+            .iter()
+            .all(|c| c.is_ascii_uppercase() || (b'2'..=b'7').contains(c))
+}
+
+//Single @ with a non empty local part and a domain containing a dot
+fn is_valid_email(id: &[u8]) -> bool {
+    let mut parts = id.split(|c| *c == b'@');
+    let (Some(local), Some(domain), None) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.len() >= 3
+        && domain.contains(&b'.')
+        && domain[0] != b'.'
+        && domain[domain.len() - 1] != b'.'
+        && id.iter().all(|c| c.is_ascii_graphic())
+}
+
+//X, Discord and Telegram identify users with numeric ids
+fn is_valid_numeric_id(id: &[u8], min_len: usize, max_len: usize) -> bool {
+    id.len() >= min_len && id.len() <= max_len && id.iter().all(|c| c.is_ascii_digit())
+}
+
+pub fn validate_profile_id(platform: Platform, profile_id: &String) -> bool {
+    let len = profile_id.len() as usize;
+    if len == 0 || len > MAX_PROFILE_ID_LEN {
+        return false;
+    }
+    let mut buf = [0u8; MAX_PROFILE_ID_LEN];
+    let id = &mut buf[..len];
+    profile_id.copy_into_slice(id);
+
+    match platform {
+        Platform::Stellar => is_valid_stellar_account(id),
+        Platform::Email => is_valid_email(id),
+        Platform::X => is_valid_numeric_id(id, 1, 20),
+        Platform::Discord => is_valid_numeric_id(id, 17, 20),
+        Platform::Telegram => is_valid_numeric_id(id, 1, 20),
+    }
+}
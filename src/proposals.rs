@@ -0,0 +1,186 @@
+use soroban_sdk::{Address, Env, Symbol, Val, Vec};
+
+use crate::{
+    balance::{read_available, read_token_balance, write_accounted, write_balance},
+    data::{
+        AuthPath, DataKey, Memo, Proposal, ProposalAction, ProposalStatus, QuestAction,
+        SignerCategory, BUMP_AMOUNT, LIFETIME_THRESHOLD, PROPOSAL_EXPIRY,
+    },
+    events::{proposal_approved, proposal_created, proposal_executed, proposal_revoked},
+    inheritance::record_activity,
+    signers::{read_signer_weight, read_threshold},
+    tokens::read_token_ids,
+    transact::send_token,
+    user_quest::record_quest_action,
+};
+
+pub fn read_proposal_count(e: &Env) -> u32 {
+    let key = DataKey::ProposalCount;
+    if let Some(count) = e.storage().persistent().get::<DataKey, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        count
+    } else {
+        0
+    }
+}
+
+fn write_proposal_count(e: &Env, count: u32) {
+    let key = DataKey::ProposalCount;
+    e.storage().persistent().set(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+fn write_proposal(e: &Env, proposal: &Proposal) {
+    let key = DataKey::Proposal(proposal.id);
+    e.storage().persistent().set(&key, proposal);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+//Returns the proposal with its current status, a pending proposal past expiry is expired
+pub fn read_proposal(e: &Env, proposal_id: u32) -> Option<Proposal> {
+    let key = DataKey::Proposal(proposal_id);
+    let mut proposal = e.storage().persistent().get::<DataKey, Proposal>(&key)?;
+    if proposal.status == ProposalStatus::Pending
+        && e.ledger().sequence() > proposal.expiration_ledger
+    {
+        proposal.status = ProposalStatus::Expired;
+    }
+    Some(proposal)
+}
+
+//Proposals are numbered from 1, the ids from start to start + limit - 1 are checked
+//and the ones with the given status are returned
+pub fn read_proposals(e: &Env, status: ProposalStatus, start: u32, limit: u32) -> Vec<Proposal> {
+    let mut proposals = Vec::new(e);
+    let count = read_proposal_count(e);
+    let first = start.max(1);
+    let last = count.min(first.saturating_add(limit).saturating_sub(1));
+    for proposal_id in first..=last {
+        if let Some(proposal) = read_proposal(e, proposal_id) {
+            if proposal.status == status {
+                proposals.push_back(proposal);
+            }
+        }
+    }
+    proposals
+}
+
+fn read_pending_proposal(e: &Env, proposal_id: u32) -> Proposal {
+    let proposal = read_proposal(e, proposal_id).expect("Proposal not found");
+    match proposal.status {
+        ProposalStatus::Pending => proposal,
+        ProposalStatus::Expired => panic!("Proposal has expired"),
+        _ => panic!("Proposal is not pending"),
+    }
+}
+
+fn require_signer(e: &Env, address: &Address) {
+    address.require_auth();
+    if read_signer_weight(e, address) == 0 {
+        panic!("Address is not a signer")
+    }
+    record_activity(e);
+}
+
+//The proposer's approval is counted, the proposal expires after PROPOSAL_EXPIRY ledgers
+pub fn create_proposal(e: &Env, proposer: Address, action: ProposalAction) -> u32 {
+    require_signer(e, &proposer);
+    let proposal_id = read_proposal_count(e) + 1;
+    let proposal = Proposal {
+        id: proposal_id,
+        proposer: proposer.clone(),
+        action,
+        approvals: Vec::from_array(e, [proposer.clone()]),
+        expiration_ledger: e.ledger().sequence() + PROPOSAL_EXPIRY,
+        status: ProposalStatus::Pending,
+    };
+    write_proposal(e, &proposal);
+    write_proposal_count(e, proposal_id);
+    proposal_created(e, proposal_id, proposer, proposal.expiration_ledger);
+    proposal_id
+}
+
+pub fn approve_proposal(e: &Env, signer: Address, proposal_id: u32) {
+    require_signer(e, &signer);
+    let mut proposal = read_pending_proposal(e, proposal_id);
+    if proposal.approvals.contains(&signer) {
+        panic!("Proposal already approved by signer")
+    }
+    proposal.approvals.push_back(signer.clone());
+    write_proposal(e, &proposal);
+    proposal_approved(e, proposal_id, signer);
+}
+
+pub fn revoke_proposal(e: &Env, proposer: Address, proposal_id: u32) {
+    proposer.require_auth();
+    let mut proposal = read_pending_proposal(e, proposal_id);
+    if proposal.proposer != proposer {
+        panic!("Only the proposer can revoke a proposal")
+    }
+    record_activity(e);
+    proposal.status = ProposalStatus::Revoked;
+    write_proposal(e, &proposal);
+    proposal_revoked(e, proposal_id);
+}
+
+//A contract call can move tokens in or out of the account, the change of the balance of every
+//registered token is recorded so the tracked balances stay in line with the token balances
+//the call cannot take the funds reserved or approved to spenders, approvals it grants itself
+//are not tracked
+fn invoke(e: &Env, contract: &Address, function: &Symbol, args: Vec<Val>) {
+    let token_ids = read_token_ids(e);
+    let mut balances = Vec::new(e);
+    for token_id in token_ids.iter() {
+        balances.push_back(read_token_balance(e, &token_id));
+    }
+    e.invoke_contract::<Val>(contract, function, args);
+    for (token_id, before) in token_ids.iter().zip(balances.iter()) {
+        let moved = read_token_balance(e, &token_id) - before;
+        if moved < 0 && read_available(e, token_id.clone()) < 0 {
+            panic!("Contract call cannot move reserved or approved funds")
+        }
+        if moved != 0 {
+            write_balance(e, token_id.clone(), moved);
+            write_accounted(e, token_id, moved);
+        }
+    }
+}
+
+//Approvals are weighted with the current signer set, so approvals of removed signers no longer count
+//the proposal is marked executed before the action runs
+pub fn execute_proposal(e: &Env, proposal_id: u32) {
+    let mut proposal = read_pending_proposal(e, proposal_id);
+    let weight: u32 = proposal
+        .approvals
+        .iter()
+        .map(|signer| read_signer_weight(e, &signer))
+        .sum();
+    //a contract call can move funds or change the account, so it needs the config threshold
+    let category = match proposal.action {
+        ProposalAction::Transfer(..) => SignerCategory::Transfer,
+        ProposalAction::Invoke(..) => SignerCategory::Config,
+    };
+    if weight < read_threshold(e, category) {
+        panic!("Proposal approvals do not meet the threshold")
+    }
+    proposal.status = ProposalStatus::Executed;
+    write_proposal(e, &proposal);
+
+    match proposal.action {
+        ProposalAction::Transfer(to, token_id, amount) => {
+            send_token(e, &to, &token_id, amount, AuthPath::Owner, Memo::None);
+            write_balance(e, token_id, -amount);
+            record_quest_action(e, QuestAction::Send, amount);
+        }
+        ProposalAction::Invoke(contract, function, args) => {
+            invoke(e, &contract, &function, args);
+        }
+    }
+    proposal_executed(e, proposal_id);
+}
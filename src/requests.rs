@@ -0,0 +1,108 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    data::{
+        Memo, PaymentRequest, PaymentRequestStatus, RequestKey, BUMP_AMOUNT, LIFETIME_THRESHOLD,
+    },
+    events::{request_cancelled, request_created, request_paid},
+    transact::check_memo,
+};
+
+pub fn read_request_count(e: &Env) -> u32 {
+    let key = RequestKey::Count;
+    if let Some(count) = e.storage().persistent().get::<RequestKey, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        count
+    } else {
+        0
+    }
+}
+
+fn write_request_count(e: &Env, count: u32) {
+    let key = RequestKey::Count;
+    e.storage().persistent().set(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+fn write_request(e: &Env, request: &PaymentRequest) {
+    let key = RequestKey::Request(request.id);
+    e.storage().persistent().set(&key, request);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+//Returns the request with its current status, an open request past expiry is expired
+pub fn read_request(e: &Env, request_id: u32) -> Option<PaymentRequest> {
+    let key = RequestKey::Request(request_id);
+    let mut request = e
+        .storage()
+        .persistent()
+        .get::<RequestKey, PaymentRequest>(&key)?;
+    if request.status == PaymentRequestStatus::Open
+        && e.ledger().sequence() > request.expiration_ledger
+    {
+        request.status = PaymentRequestStatus::Expired;
+    }
+    Some(request)
+}
+
+pub fn create_request(
+    e: &Env,
+    token_id: Address,
+    amount: i128,
+    expiration_ledger: u32,
+    memo: Memo,
+) -> u32 {
+    if amount <= 0 {
+        panic!("Request amount must be greater than zero")
+    }
+    if expiration_ledger <= e.ledger().sequence() {
+        panic!("Request expiration must be in the future")
+    }
+    check_memo(&memo);
+    let request_id = read_request_count(e) + 1;
+    let request = PaymentRequest {
+        id: request_id,
+        token_id: token_id.clone(),
+        amount,
+        expiration_ledger,
+        memo,
+        status: PaymentRequestStatus::Open,
+        payer: None,
+    };
+    write_request(e, &request);
+    write_request_count(e, request_id);
+    request_created(e, request_id, token_id, amount);
+    request_id
+}
+
+fn read_open_request(e: &Env, request_id: u32) -> PaymentRequest {
+    let request = read_request(e, request_id).expect("Payment request not found");
+    match request.status {
+        PaymentRequestStatus::Open => request,
+        PaymentRequestStatus::Expired => panic!("Payment request has expired"),
+        _ => panic!("Payment request is not open"),
+    }
+}
+
+//Marks the request paid by the payer, the caller then takes the payment like a receive
+pub fn settle_request(e: &Env, request_id: u32, payer: Address) -> PaymentRequest {
+    let mut request = read_open_request(e, request_id);
+    request.status = PaymentRequestStatus::Paid;
+    request.payer = Some(payer.clone());
+    write_request(e, &request);
+    request_paid(e, request_id, payer, request.amount);
+    request
+}
+
+pub fn cancel_request(e: &Env, request_id: u32) {
+    let mut request = read_open_request(e, request_id);
+    request.status = PaymentRequestStatus::Cancelled;
+    write_request(e, &request);
+    request_cancelled(e, request_id);
+}
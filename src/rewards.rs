@@ -0,0 +1,108 @@
+use soroban_sdk::{token, Env, Vec};
+
+use crate::{
+    access::read_controller,
+    balance::{write_accounted, write_balance},
+    data::{DataKey, Redemption, RewardOffer, BUMP_AMOUNT, LIFETIME_THRESHOLD},
+    events::points_redeemed,
+    tokens::add_token,
+    user_quest::{read_quest_data, write_quest_data},
+};
+
+pub fn read_reward_offer(e: &Env, offer_id: u32) -> Option<RewardOffer> {
+    let key = DataKey::RewardOffer(offer_id);
+    e.storage().instance().get(&key)
+}
+
+pub fn write_reward_offer(e: &Env, offer_id: u32, offer: RewardOffer) {
+    let key = DataKey::RewardOffer(offer_id);
+    e.storage().instance().set(&key, &offer);
+}
+
+pub fn remove_reward_offer(e: &Env, offer_id: u32) {
+    let key = DataKey::RewardOffer(offer_id);
+    e.storage().instance().remove(&key);
+}
+
+pub fn read_redemption_count(e: &Env) -> u32 {
+    let key = DataKey::RedemptionCount;
+    if let Some(count) = e.storage().persistent().get::<DataKey, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        count
+    } else {
+        0
+    }
+}
+
+fn write_redemption(e: &Env, redemption: Redemption) {
+    let index = read_redemption_count(e) + 1;
+    let key = DataKey::Redemption(index);
+    e.storage().persistent().set(&key, &redemption);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+
+    let count_key = DataKey::RedemptionCount;
+    e.storage().persistent().set(&count_key, &index);
+    e.storage()
+        .persistent()
+        .extend_ttl(&count_key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+//Redemptions are numbered from 1, start is the first index returned
+pub fn read_redemptions(e: &Env, start: u32, limit: u32) -> Vec<Redemption> {
+    let mut redemptions = Vec::new(e);
+    let count = read_redemption_count(e);
+    let first = start.max(1);
+    let last = count.min(first.saturating_add(limit).saturating_sub(1));
+    for index in first..=last {
+        let key = DataKey::Redemption(index);
+        if let Some(redemption) = e.storage().persistent().get::<DataKey, Redemption>(&key) {
+            redemptions.push_back(redemption);
+        }
+    }
+    redemptions
+}
+
+//Debits the offer cost from the quest points and pulls the reward from the controller
+//in the same invocation, so points are never spent without the reward being paid
+pub fn redeem_offer(e: &Env, offer_id: u32) {
+    let offer = read_reward_offer(e, offer_id).expect("Reward offer not found");
+    if e.ledger().sequence() > offer.expiration_ledger {
+        panic!("Reward offer has expired")
+    }
+
+    let mut user_data = read_quest_data(e);
+    if user_data.points < offer.cost {
+        panic!("Not enough points to redeem this offer")
+    }
+    user_data.points -= offer.cost;
+    write_quest_data(e, user_data.clone());
+
+    let controller = read_controller(e);
+    let contract_address = e.current_contract_address();
+    let token = token::Client::new(e, &offer.token_id);
+    token.transfer_from(
+        &contract_address,
+        &controller,
+        &contract_address,
+        &offer.amount,
+    );
+    write_accounted(e, offer.token_id.clone(), offer.amount);
+    write_balance(e, offer.token_id.clone(), offer.amount);
+    add_token(e, offer.token_id.clone());
+
+    write_redemption(
+        e,
+        Redemption {
+            offer_id,
+            cost: offer.cost,
+            token_id: offer.token_id,
+            amount: offer.amount,
+            ledger: e.ledger().sequence(),
+        },
+    );
+    points_redeemed(e, offer_id, offer.cost, user_data.points);
+}
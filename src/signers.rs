@@ -0,0 +1,154 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{
+    data::{DataKey, Signer, SignerCategory, MAX_SIGNERS},
+    inheritance::record_activity,
+    upgrade::migrate_legacy_owner,
+};
+
+const CATEGORIES: [SignerCategory; 3] = [
+    SignerCategory::Transfer,
+    SignerCategory::Config,
+    SignerCategory::Upgrade,
+];
+
+//Weighted signers of the account, they replace the single owner address
+//the owner of an account deployed before them becomes its only signer on the first read
+pub fn read_signers(e: &Env) -> Vec<Signer> {
+    let key = DataKey::Signers;
+    if let Some(signers) = e.storage().instance().get(&key) {
+        return signers;
+    }
+    if migrate_legacy_owner(e) {
+        return read_signers(e);
+    }
+    Vec::new(e)
+}
+
+fn write_signers(e: &Env, signers: &Vec<Signer>) {
+    let key = DataKey::Signers;
+    e.storage().instance().set(&key, signers);
+}
+
+pub fn has_signers(e: &Env) -> bool {
+    !read_signers(e).is_empty()
+}
+
+pub fn read_signer_weight(e: &Env, address: &Address) -> u32 {
+    read_signers(e)
+        .iter()
+        .find(|signer| signer.address == *address)
+        .map(|signer| signer.weight)
+        .unwrap_or(0)
+}
+
+pub fn read_threshold(e: &Env, category: SignerCategory) -> u32 {
+    let key = DataKey::SignerThreshold(category);
+    e.storage().instance().get(&key).unwrap_or(1)
+}
+
+//The owner of a single signer account, None once the account has several signers
+pub fn read_owner(e: &Env) -> Option<Address> {
+    let signers = read_signers(e);
+    if signers.len() != 1 {
+        return None;
+    }
+    signers.get(0).map(|signer| signer.address)
+}
+
+//Makes the address the only signer with every threshold at 1, as for a single owner account
+pub fn write_owner(e: &Env, owner_id: &Address) {
+    let signer = Signer {
+        address: owner_id.clone(),
+        weight: 1,
+    };
+    write_signers(e, &Vec::from_array(e, [signer]));
+    for category in CATEGORIES {
+        let key = DataKey::SignerThreshold(category);
+        e.storage().instance().remove(&key);
+    }
+}
+
+//Every threshold must stay reachable by the signer set
+fn check_signer_set(e: &Env, signers: &Vec<Signer>) {
+    if signers.len() > MAX_SIGNERS {
+        panic!("Maximum number of signers reached")
+    }
+    let total_weight: u32 = signers.iter().map(|signer| signer.weight).sum();
+    for category in CATEGORIES {
+        if read_threshold(e, category) > total_weight {
+            panic!("Signer threshold cannot exceed the total weight")
+        }
+    }
+}
+
+fn signer_index(signers: &Vec<Signer>, address: &Address) -> Option<u32> {
+    signers
+        .iter()
+        .position(|signer| signer.address == *address)
+        .map(|index| index as u32)
+}
+
+pub fn add_signer(e: &Env, address: Address, weight: u32) {
+    if weight == 0 {
+        panic!("Signer weight must be greater than zero")
+    }
+    let mut signers = read_signers(e);
+    if signer_index(&signers, &address).is_some() {
+        panic!("Address is already a signer")
+    }
+    signers.push_back(Signer { address, weight });
+    check_signer_set(e, &signers);
+    write_signers(e, &signers);
+}
+
+pub fn remove_signer(e: &Env, address: Address) {
+    let mut signers = read_signers(e);
+    let index = signer_index(&signers, &address).expect("Address is not a signer");
+    signers.remove(index);
+    check_signer_set(e, &signers);
+    write_signers(e, &signers);
+}
+
+pub fn update_signer_weight(e: &Env, address: Address, weight: u32) {
+    if weight == 0 {
+        panic!("Signer weight must be greater than zero")
+    }
+    let mut signers = read_signers(e);
+    let index = signer_index(&signers, &address).expect("Address is not a signer");
+    signers.set(index, Signer { address, weight });
+    check_signer_set(e, &signers);
+    write_signers(e, &signers);
+}
+
+pub fn write_threshold(e: &Env, category: SignerCategory, threshold: u32) {
+    if threshold == 0 {
+        panic!("Signer threshold must be greater than zero")
+    }
+    let key = DataKey::SignerThreshold(category);
+    e.storage().instance().set(&key, &threshold);
+    check_signer_set(e, &read_signers(e));
+}
+
+//The listed signers must all authorize the call and their weights must meet the category threshold
+pub fn require_signers(e: &Env, category: SignerCategory, signers: Vec<Address>) {
+    if !has_signers(e) {
+        panic!("Owner not set")
+    }
+    let mut weight = 0;
+    for (index, address) in signers.iter().enumerate() {
+        if signers.first_index_of(&address) != Some(index as u32) {
+            panic!("Duplicate signer")
+        }
+        let signer_weight = read_signer_weight(e, &address);
+        if signer_weight == 0 {
+            panic!("Address is not a signer")
+        }
+        address.require_auth();
+        weight += signer_weight;
+    }
+    if weight < read_threshold(e, category) {
+        panic!("Signer threshold not met")
+    }
+    record_activity(e);
+}
@@ -0,0 +1,142 @@
+use soroban_sdk::Env;
+
+use crate::{
+    balance::{read_available, write_balance, write_reserved},
+    data::{AuthPath, DataKey, Memo, Stream, StreamTerms, BUMP_AMOUNT, LIFETIME_THRESHOLD},
+    events::{stream_cancelled, stream_created, stream_withdrawn},
+    transact::send_token,
+};
+
+pub fn read_stream_count(e: &Env) -> u32 {
+    let key = DataKey::StreamCount;
+    if let Some(count) = e.storage().persistent().get::<DataKey, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        count
+    } else {
+        0
+    }
+}
+
+fn write_stream_count(e: &Env, count: u32) {
+    let key = DataKey::StreamCount;
+    e.storage().persistent().set(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn read_stream(e: &Env, stream_id: u32) -> Option<Stream> {
+    let key = DataKey::Stream(stream_id);
+    e.storage().persistent().get(&key)
+}
+
+fn write_stream(e: &Env, stream: &Stream) {
+    let key = DataKey::Stream(stream.id);
+    e.storage().persistent().set(&key, stream);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn stream_total(terms: &StreamTerms) -> i128 {
+    terms.rate_per_ledger * (terms.end_ledger - terms.start_ledger) as i128
+}
+
+//Amount accrued to the recipient so far, nothing accrues before the cliff
+fn stream_accrued(e: &Env, terms: &StreamTerms) -> i128 {
+    let ledger = e.ledger().sequence();
+    if ledger < terms.cliff_ledger || ledger <= terms.start_ledger {
+        return 0;
+    }
+    let elapsed = ledger.min(terms.end_ledger) - terms.start_ledger;
+    terms.rate_per_ledger * elapsed as i128
+}
+
+//Accrued amount the recipient has not withdrawn yet, zero once the stream is cancelled
+pub fn read_withdrawable(e: &Env, stream: &Stream) -> i128 {
+    if stream.cancelled {
+        return 0;
+    }
+    stream_accrued(e, &stream.terms) - stream.withdrawn
+}
+
+//The full amount of the stream is reserved from the unreserved balance of the token
+pub fn create_stream(e: &Env, terms: StreamTerms) -> u32 {
+    if terms.rate_per_ledger <= 0 {
+        panic!("Stream rate must be greater than zero")
+    }
+    if terms.start_ledger >= terms.end_ledger
+        || terms.cliff_ledger < terms.start_ledger
+        || terms.cliff_ledger > terms.end_ledger
+    {
+        panic!("Invalid stream schedule")
+    }
+    if terms.start_ledger < e.ledger().sequence() {
+        panic!("Stream cannot start in the past")
+    }
+    let total = stream_total(&terms);
+    if total > read_available(e, terms.token_id.clone()) {
+        panic!("Not enough unreserved balance for the stream")
+    }
+    write_reserved(e, terms.token_id.clone(), total);
+
+    let stream_id = read_stream_count(e) + 1;
+    let stream = Stream {
+        id: stream_id,
+        terms,
+        withdrawn: 0,
+        cancelled: false,
+    };
+    write_stream(e, &stream);
+    write_stream_count(e, stream_id);
+    stream_created(e, stream_id, stream.terms.recipient, total);
+    stream_id
+}
+
+fn pay_recipient(e: &Env, stream: &mut Stream, amount: i128) {
+    let terms = &stream.terms;
+    write_reserved(e, terms.token_id.clone(), -amount);
+    send_token(
+        e,
+        &terms.recipient,
+        &terms.token_id,
+        amount,
+        AuthPath::Stream,
+        Memo::None,
+    );
+    write_balance(e, terms.token_id.clone(), -amount);
+    stream.withdrawn += amount;
+}
+
+//The recipient withdraws everything accrued so far
+pub fn withdraw_stream(e: &Env, stream_id: u32) -> i128 {
+    let mut stream = read_stream(e, stream_id).expect("Stream not found");
+    stream.terms.recipient.require_auth();
+    let amount = read_withdrawable(e, &stream);
+    if amount <= 0 {
+        panic!("Nothing to withdraw from the stream")
+    }
+    pay_recipient(e, &mut stream, amount);
+    write_stream(e, &stream);
+    stream_withdrawn(e, stream_id, amount);
+    amount
+}
+
+//Pays the recipient what has accrued and releases the rest of the reserved amount
+pub fn cancel_stream(e: &Env, stream_id: u32) {
+    let mut stream = read_stream(e, stream_id).expect("Stream not found");
+    if stream.cancelled {
+        panic!("Stream has already been cancelled")
+    }
+    let accrued = read_withdrawable(e, &stream);
+    if accrued > 0 {
+        pay_recipient(e, &mut stream, accrued);
+    }
+    let unaccrued = stream_total(&stream.terms) - stream.withdrawn;
+    write_reserved(e, stream.terms.token_id.clone(), -unaccrued);
+    stream.cancelled = true;
+    write_stream(e, &stream);
+    stream_cancelled(e, stream_id, accrued, unaccrued);
+}
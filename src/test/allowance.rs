@@ -0,0 +1,221 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, fund_account,
+    sign_pkey, MAX_ALLOWANCE, PRIMARY, WRONG,
+};
+use crate::data::Memo;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+
+#[test]
+fn test_set_allowance_with_addr() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+
+    setup.client.set_allowance_with_addr(&setup.signers, &250);
+    assert_eq!(setup.client.get_allowance(), 250);
+}
+
+#[test]
+#[should_panic]
+fn test_set_allowance_with_addr_without_auth() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+
+    setup.client.set_allowance_with_addr(&setup.signers, &250);
+}
+
+#[test]
+fn test_set_allowance_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_allowance_pkey",
+        250_i128,
+    );
+    setup
+        .client
+        .set_allowance_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &250);
+    assert_eq!(setup.client.get_allowance(), 250);
+}
+
+#[test]
+fn test_set_allowance_wrong_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let auth = sign_pkey(&env, &setup.client, &WRONG, "set_allowance_pkey", 250_i128);
+    assert!(!setup.client.set_allowance_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &250
+    ));
+    assert_eq!(setup.client.get_allowance(), MAX_ALLOWANCE);
+}
+
+#[test]
+fn test_set_allowance_signature_for_other_action() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    //a valid signature only authorizes the action and arguments it was made for
+    let auth = sign_pkey(&env, &setup.client, &PRIMARY, "set_allowance_pkey", 10_i128);
+    assert!(!setup.client.set_allowance_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &250
+    ));
+
+    let mut proof = auth.proof.to_array();
+    proof[64] = 9;
+    assert!(!setup.client.set_allowance_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &BytesN::from_array(&env, &proof),
+        &10
+    ));
+    assert_eq!(setup.client.get_allowance(), MAX_ALLOWANCE);
+}
+
+#[test]
+#[should_panic]
+fn test_set_allowance_pkey_without_executor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_allowance_pkey",
+        250_i128,
+    );
+    setup
+        .client
+        .set_allowance_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &250);
+}
+
+#[test]
+fn test_send_up_to_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 5_000);
+    let recipient = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "send_with_pkey",
+        (
+            recipient.clone(),
+            token.address.clone(),
+            MAX_ALLOWANCE,
+            Memo::None,
+        ),
+    );
+    setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &MAX_ALLOWANCE,
+        &Memo::None,
+    );
+    assert_eq!(token.balance(&recipient), MAX_ALLOWANCE);
+}
+
+#[test]
+#[should_panic(expected = "You cannot send an amount greater than your allowance")]
+fn test_send_above_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 5_000);
+
+    let recipient = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "send_with_pkey",
+        (
+            recipient.clone(),
+            token.address.clone(),
+            MAX_ALLOWANCE + 1,
+            Memo::None,
+        ),
+    );
+    setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &(MAX_ALLOWANCE + 1),
+        &Memo::None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "You cannot send an amount greater than your allowance")]
+fn test_send_above_lowered_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 5_000);
+
+    let recipient = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_allowance_pkey",
+        100_i128,
+    );
+    setup
+        .client
+        .set_allowance_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &100);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "send_with_pkey",
+        (
+            recipient.clone(),
+            token.address.clone(),
+            101_i128,
+            Memo::None,
+        ),
+    );
+    setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &101,
+        &Memo::None,
+    );
+}
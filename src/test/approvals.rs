@@ -0,0 +1,337 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, fund_account,
+    sign_pkey, MAX_ALLOWANCE, PRIMARY,
+};
+use crate::data::{SignerCategory, SpenderApproval};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    vec, Address, Env, IntoVal, String,
+};
+
+#[test]
+fn test_approve_spender_addr() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let spender = Address::generate(&env);
+
+    setup
+        .client
+        .approve_spender_addr(&setup.signers, &token.address, &spender, &300, &1_000);
+
+    assert_eq!(token.allowance(&setup.client.address, &spender), 300);
+    assert_eq!(
+        setup.client.get_spender_approvals(),
+        vec![
+            &env,
+            SpenderApproval {
+                token_id: token.address.clone(),
+                spender: spender.clone(),
+                amount: 300,
+                expiration_ledger: 1_000,
+            }
+        ]
+    );
+
+    token.transfer_from(&spender, &setup.client.address, &spender, &100);
+    assert_eq!(token.balance(&spender), 100);
+}
+
+#[test]
+fn test_spender_pulls_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let spender = Address::generate(&env);
+    setup
+        .client
+        .approve_spender_addr(&setup.signers, &token.address, &spender, &500, &1_000);
+
+    token.transfer_from(&spender, &setup.client.address, &spender, &500);
+    assert_eq!(setup.client.get_balance(&token.address), 0);
+    assert_eq!(setup.client.get_tokens().len(), 0);
+
+    //the pull is recorded before the direct transfer is credited
+    token_admin.mint(&setup.client.address, &100);
+    assert_eq!(setup.client.credit_deposit(&token.address), 100);
+    assert_eq!(setup.client.get_balance(&token.address), 100);
+    assert_eq!(setup.client.get_spender_approvals().len(), 0);
+}
+
+#[test]
+fn test_replace_partly_pulled_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let spender = Address::generate(&env);
+    setup
+        .client
+        .approve_spender_addr(&setup.signers, &token.address, &spender, &300, &1_000);
+    token.transfer_from(&spender, &setup.client.address, &spender, &200);
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+
+    setup
+        .client
+        .approve_spender_addr(&setup.signers, &token.address, &spender, &50, &1_000);
+
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+    assert_eq!(
+        setup.client.get_spender_approvals().get(0).unwrap().amount,
+        50
+    );
+}
+
+#[test]
+fn test_replace_and_remove_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let spender = Address::generate(&env);
+
+    setup
+        .client
+        .approve_spender_addr(&setup.signers, &token.address, &spender, &300, &1_000);
+    setup
+        .client
+        .approve_spender_addr(&setup.signers, &token.address, &spender, &50, &1_000);
+    let approvals = setup.client.get_spender_approvals();
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals.get(0).unwrap().amount, 50);
+
+    setup
+        .client
+        .approve_spender_addr(&setup.signers, &token.address, &spender, &0, &1_000);
+    assert_eq!(setup.client.get_spender_approvals().len(), 0);
+    assert_eq!(token.allowance(&setup.client.address, &spender), 0);
+}
+
+#[test]
+fn test_approve_spender_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let spender = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "approve_spender_pkey",
+        (token.address.clone(), spender.clone(), 300_i128, 1_000_u32),
+    );
+    assert!(setup.client.approve_spender_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &token.address,
+        &spender,
+        &300,
+        &1_000,
+    ));
+    assert_eq!(token.allowance(&setup.client.address, &spender), 300);
+}
+
+#[test]
+#[should_panic(expected = "You cannot approve an amount greater than your allowance")]
+fn test_approve_spender_pkey_above_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    let spender = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "approve_spender_pkey",
+        (
+            token.address.clone(),
+            spender.clone(),
+            MAX_ALLOWANCE + 1,
+            1_000_u32,
+        ),
+    );
+    setup.client.approve_spender_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &token.address,
+        &spender,
+        &(MAX_ALLOWANCE + 1),
+        &1_000,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Approvals exceed the unreserved balance")]
+fn test_approvals_cannot_cover_reserved_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "holiday"),
+        &token.address,
+        &0,
+        &0,
+    );
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &300);
+    setup.client.approve_spender_addr(
+        &setup.signers,
+        &token.address,
+        &Address::generate(&env),
+        &150,
+        &1_000,
+    );
+
+    //150 is already approved and 300 is in the vault
+    setup.client.approve_spender_addr(
+        &setup.signers,
+        &token.address,
+        &Address::generate(&env),
+        &51,
+        &1_000,
+    );
+}
+
+#[test]
+#[should_panic(expected = "You cannot approve an amount greater than your allowance")]
+fn test_approve_spenders_pkey_add_up_to_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 5_000);
+
+    for amount in [600_i128, 401] {
+        let spender = Address::generate(&env);
+        let auth = sign_pkey(
+            &env,
+            &setup.client,
+            &PRIMARY,
+            "approve_spender_pkey",
+            (token.address.clone(), spender.clone(), amount, 1_000_u32),
+        );
+        setup.client.approve_spender_pkey(
+            &1,
+            &auth.nonce,
+            &auth.expiration_ledger,
+            &auth.proof,
+            &token.address,
+            &spender,
+            &amount,
+            &1_000,
+        );
+    }
+}
+
+#[test]
+fn test_revoke_all() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let (other_token, other_token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    fund_account(&env, &setup.client, &other_token_admin, 500);
+    let spender = Address::generate(&env);
+    let other_spender = Address::generate(&env);
+    setup
+        .client
+        .approve_spender_addr(&setup.signers, &token.address, &spender, &300, &1_000);
+    setup.client.approve_spender_addr(
+        &setup.signers,
+        &other_token.address,
+        &other_spender,
+        &200,
+        &1_000,
+    );
+
+    //one signer can revoke even when transfers need more weight
+    let signer = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Transfer, &2);
+    setup.client.revoke_all_addr(&signer);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (symbol_short!("spender"), symbol_short!("revoked")).into_val(&env),
+                2_u32.into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(token.allowance(&setup.client.address, &spender), 0);
+    assert_eq!(
+        other_token.allowance(&setup.client.address, &other_spender),
+        0
+    );
+    assert_eq!(setup.client.get_spender_approvals().len(), 0);
+}
+
+#[test]
+fn test_revoke_all_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let spender = Address::generate(&env);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "approve_spender_pkey",
+        (token.address.clone(), spender.clone(), 300_i128, 1_000_u32),
+    );
+    setup.client.approve_spender_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &token.address,
+        &spender,
+        &300,
+        &1_000,
+    );
+
+    let auth = sign_pkey(&env, &setup.client, &PRIMARY, "revoke_all_pkey", ());
+    assert!(setup
+        .client
+        .revoke_all_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof));
+    assert_eq!(token.allowance(&setup.client.address, &spender), 0);
+}
+
+#[test]
+#[should_panic(expected = "Address is not a signer")]
+fn test_revoke_all_by_non_signer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+
+    setup.client.revoke_all_addr(&Address::generate(&env));
+}
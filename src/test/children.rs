@@ -0,0 +1,257 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, fund_account,
+    sign_pkey, MAX_ALLOWANCE, PRIMARY,
+};
+use crate::data::Memo;
+use soroban_sdk::{
+    map,
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+#[test]
+fn test_child_send_within_budget() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let member = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let child_id = setup.client.create_child_addr(
+        &setup.signers,
+        &member,
+        &map![&env, (token.address.clone(), 150_i128)],
+        &1_000,
+    );
+    setup
+        .client
+        .child_send(&child_id, &recipient, &token.address, &100, &Memo::None);
+    setup
+        .client
+        .child_send(&child_id, &recipient, &token.address, &50, &Memo::None);
+
+    assert_eq!(token.balance(&recipient), 150);
+    assert_eq!(setup.client.get_balance(&token.address), 350);
+    let child = setup.client.get_child(&child_id).unwrap();
+    assert_eq!(child.spent.get(token.address.clone()), Some(150));
+    assert_eq!(setup.client.get_children().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Amount exceeds the child budget")]
+fn test_child_send_above_budget() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let child_id = setup.client.create_child_addr(
+        &setup.signers,
+        &Address::generate(&env),
+        &map![&env, (token.address.clone(), 150_i128)],
+        &1_000,
+    );
+    let recipient = Address::generate(&env);
+    setup
+        .client
+        .child_send(&child_id, &recipient, &token.address, &100, &Memo::None);
+
+    setup
+        .client
+        .child_send(&child_id, &recipient, &token.address, &51, &Memo::None);
+}
+
+#[test]
+#[should_panic(expected = "Amount exceeds the child budget")]
+fn test_child_send_token_without_budget() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let (other_token, _) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let child_id = setup.client.create_child_addr(
+        &setup.signers,
+        &Address::generate(&env),
+        &map![&env, (other_token.address.clone(), 150_i128)],
+        &1_000,
+    );
+
+    setup.client.child_send(
+        &child_id,
+        &Address::generate(&env),
+        &token.address,
+        &1,
+        &Memo::None,
+    );
+}
+
+#[test]
+fn test_revoked_child_slot_is_reused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+    let budgets = map![&env, (token.address.clone(), 150_i128)];
+    for _ in 0..20 {
+        let child_id = setup.client.create_child_addr(
+            &setup.signers,
+            &Address::generate(&env),
+            &budgets,
+            &1_000,
+        );
+        setup.client.revoke_child_addr(&setup.signers, &child_id);
+    }
+
+    let member = Address::generate(&env);
+    let child_id = setup
+        .client
+        .create_child_addr(&setup.signers, &member, &budgets, &1_000);
+    assert_eq!(child_id, 1);
+    let child = setup.client.get_child(&child_id).unwrap();
+    assert_eq!(child.signer, member);
+    assert!(!child.revoked);
+    assert_eq!(setup.client.get_children().len(), 20);
+}
+
+#[test]
+#[should_panic(expected = "Maximum number of child slots reached")]
+fn test_maximum_active_child_slots() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+    let budgets = map![&env, (token.address.clone(), 150_i128)];
+    for _ in 0..21 {
+        setup
+            .client
+            .create_child_addr(&setup.signers, &Address::generate(&env), &budgets, &1_000);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Child slot has been revoked")]
+fn test_revoked_child_cannot_send() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let child_id = setup.client.create_child_addr(
+        &setup.signers,
+        &Address::generate(&env),
+        &map![&env, (token.address.clone(), 150_i128)],
+        &1_000,
+    );
+    setup.client.revoke_child_addr(&setup.signers, &child_id);
+    assert!(setup.client.get_child(&child_id).unwrap().revoked);
+
+    setup.client.child_send(
+        &child_id,
+        &Address::generate(&env),
+        &token.address,
+        &1,
+        &Memo::None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Child slot has expired")]
+fn test_expired_child_cannot_send() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let child_id = setup.client.create_child_addr(
+        &setup.signers,
+        &Address::generate(&env),
+        &map![&env, (token.address.clone(), 150_i128)],
+        &1_000,
+    );
+    env.ledger().with_mut(|li| li.sequence_number = 1_001);
+
+    setup.client.child_send(
+        &child_id,
+        &Address::generate(&env),
+        &token.address,
+        &1,
+        &Memo::None,
+    );
+}
+
+#[test]
+fn test_create_and_revoke_child_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    let member = Address::generate(&env);
+    let budgets = map![&env, (token.address.clone(), 150_i128)];
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_child_pkey",
+        (member.clone(), budgets.clone(), 1_000_u32),
+    );
+    let child_id = setup.client.create_child_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &member,
+        &budgets,
+        &1_000,
+    );
+    assert_eq!(child_id, Some(1));
+    let child = setup.client.get_child(&1).unwrap();
+    assert_eq!(child.signer, member);
+    assert_eq!(child.budgets, budgets);
+
+    let auth = sign_pkey(&env, &setup.client, &PRIMARY, "revoke_child_pkey", 1_u32);
+    assert!(setup.client.revoke_child_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &1
+    ));
+    assert!(setup.client.get_children().get(0).unwrap().revoked);
+}
+
+#[test]
+#[should_panic(expected = "You cannot give a budget greater than your allowance")]
+fn test_create_child_pkey_above_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    let (other_token, _) = create_token(&env);
+    let member = Address::generate(&env);
+    let budgets = map![
+        &env,
+        (token.address.clone(), 150_i128),
+        (other_token.address.clone(), MAX_ALLOWANCE + 1)
+    ];
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_child_pkey",
+        (member.clone(), budgets.clone(), 1_000_u32),
+    );
+    setup.client.create_child_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &member,
+        &budgets,
+        &1_000,
+    );
+}
@@ -0,0 +1,175 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, set_owner_pkey,
+    sign_pkey, try_set_allowance, Credential, PLATFORM, PRIMARY, PROFILE_ID, WRONG,
+};
+use crate::{data::ProfileEncrypted, SubAccountClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+const NEW_PASSKEY_SECRET: [u8; 32] = [4; 32];
+
+const ROTATED: Credential = Credential {
+    platform: PLATFORM,
+    profile_id: PROFILE_ID,
+    secret_key: NEW_PASSKEY_SECRET,
+};
+
+fn rotated_profile(env: &Env) -> ProfileEncrypted {
+    ProfileEncrypted {
+        platform: PLATFORM,
+        profile_id: String::from_str(env, PROFILE_ID),
+        gen_salt_encrypted: String::from_str(env, "new_salt"),
+        salt_encryption_iv: String::from_str(env, "new_salt_iv"),
+        index_encrypted: String::from_str(env, "new_key_index"),
+        index_encryption_iv: String::from_str(env, "new_index_iv"),
+    }
+}
+
+fn rotate_pkey(env: &Env, client: &SubAccountClient, credential: &Credential) -> bool {
+    let profile = rotated_profile(env);
+    let passkey = ROTATED.passkey(env);
+    let auth = sign_pkey(
+        env,
+        client,
+        credential,
+        "rotate_credentials_pkey",
+        (profile.clone(), passkey.clone()),
+    );
+    client.rotate_credentials_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &profile,
+        &passkey,
+    )
+}
+
+#[test]
+fn test_rotate_credentials_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    assert_eq!(setup.client.get_credential_epoch(), 0);
+
+    assert!(rotate_pkey(&env, &setup.client, &PRIMARY));
+
+    assert_eq!(setup.client.get_credential_epoch(), 1);
+    let keys = setup
+        .client
+        .get_encrypted_keys(&PLATFORM, &String::from_str(&env, PROFILE_ID));
+    assert_eq!(keys.gen_salt_encrypted, String::from_str(&env, "new_salt"));
+    assert_eq!(
+        keys.salt_encryption_iv,
+        String::from_str(&env, "new_salt_iv")
+    );
+    assert_eq!(
+        keys.index_encrypted,
+        String::from_str(&env, "new_key_index")
+    );
+    assert_eq!(
+        keys.index_encryption_iv,
+        String::from_str(&env, "new_index_iv")
+    );
+
+    //old passkey is rejected, new passkey is accepted
+    assert!(!try_set_allowance(&env, &setup.client, &PRIMARY, 50));
+    assert!(try_set_allowance(&env, &setup.client, &ROTATED, 50));
+    assert_eq!(setup.client.get_allowance(), 50);
+}
+
+#[test]
+fn test_rotate_credentials_clears_tx_nonce() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    let spender = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_tx_nonce",
+        (spender.clone(), token.address.clone(), 100_i128),
+    );
+    setup.client.create_tx_nonce(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &spender,
+        &token.address,
+        &100,
+    );
+    assert!(!setup.client.get_tx_nonce().is_empty());
+
+    assert!(rotate_pkey(&env, &setup.client, &PRIMARY));
+    assert!(setup.client.get_tx_nonce().is_empty());
+}
+
+#[test]
+fn test_rotate_credentials_keeps_nonce() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let profile_id = String::from_str(&env, PROFILE_ID);
+
+    assert!(rotate_pkey(&env, &setup.client, &PRIMARY));
+    assert_eq!(setup.client.get_nonce(&PLATFORM, &profile_id), 1);
+
+    //a proof from the old passkey for the next nonce stays invalid
+    assert!(!try_set_allowance(&env, &setup.client, &PRIMARY, 50));
+    assert!(try_set_allowance(&env, &setup.client, &ROTATED, 50));
+    assert_eq!(setup.client.get_nonce(&PLATFORM, &profile_id), 2);
+}
+
+#[test]
+fn test_rotate_credentials_addr() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let owner = Address::generate(&env);
+    set_owner_pkey(&env, &setup.client, &owner);
+
+    setup.client.rotate_credentials_addr(
+        &vec![&env, owner.clone()],
+        &rotated_profile(&env),
+        &ROTATED.passkey(&env),
+    );
+
+    assert_eq!(setup.client.get_credential_epoch(), 1);
+    assert!(try_set_allowance(&env, &setup.client, &ROTATED, 50));
+}
+
+#[test]
+fn test_rotate_credentials_wrong_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    assert!(!rotate_pkey(&env, &setup.client, &WRONG));
+    assert_eq!(setup.client.get_credential_epoch(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_rotate_credentials_pkey_without_executor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+
+    assert!(rotate_pkey(&env, &setup.client, &PRIMARY));
+}
+
+#[test]
+#[should_panic(expected = "Profile is not linked to this account")]
+fn test_rotate_credentials_unknown_profile() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+
+    setup.client.rotate_credentials_addr(
+        &setup.signers,
+        &rotated_profile(&env),
+        &ROTATED.passkey(&env),
+    );
+}
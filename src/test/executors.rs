@@ -0,0 +1,37 @@
+use super::{create_account_with_address, create_account_with_profile};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[test]
+fn test_executor_is_set() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+
+    assert_eq!(setup.client.get_executor(&1), setup.executor);
+}
+
+#[test]
+#[should_panic(expected = "Executors have already been set")]
+fn test_set_executor_after_done() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+
+    setup.client.set_executor(&2, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Executors have already been set and flagged as done.")]
+fn test_set_executor_done_twice() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+
+    setup.client.set_executor_done();
+}
+
+#[test]
+#[should_panic]
+fn test_get_unknown_executor() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+
+    setup.client.get_executor(&2);
+}
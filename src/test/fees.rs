@@ -0,0 +1,189 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, env_with_long_ttl,
+    fund_account, sign_pkey, try_set_allowance, PRIMARY, WRONG,
+};
+use crate::{
+    data::{AuthPath, FeeConfig, FeeSpending, TransactionDirection, DAY_IN_LEDGERS},
+    SubAccountClient,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Events, Ledger},
+    vec, Address, Env, IntoVal,
+};
+
+fn set_fee_config(env: &Env, client: &SubAccountClient, config: Option<FeeConfig>) {
+    let auth = sign_pkey(env, client, &PRIMARY, "set_fee_config_pkey", config.clone());
+    assert!(client.set_fee_config_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &config
+    ));
+}
+
+fn fee_config(token_id: &Address, fee_per_action: i128, daily_cap: i128) -> FeeConfig {
+    FeeConfig {
+        token_id: token_id.clone(),
+        fee_per_action,
+        daily_cap,
+    }
+}
+
+#[test]
+fn test_executor_reimbursed_for_pkey_action() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let config = fee_config(&token.address, 10, 100);
+    set_fee_config(&env, &setup.client, Some(config.clone()));
+    assert_eq!(setup.client.get_fee_config(), Some(config));
+    //the configuration call itself is already sponsored
+    assert_eq!(token.balance(&setup.executor), 10);
+
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 50));
+    assert_eq!(token.balance(&setup.executor), 20);
+    assert_eq!(setup.client.get_balance(&token.address), 480);
+    assert_eq!(
+        setup.client.get_fee_spending(),
+        FeeSpending {
+            day: env.ledger().sequence() / DAY_IN_LEDGERS,
+            spent: 20,
+        }
+    );
+    //reimbursements show up in the transfer history
+    let record = setup.client.get_transactions(&0, &1).get(0).unwrap();
+    assert_eq!(record.direction, TransactionDirection::Sent);
+    assert_eq!(record.counterparty, setup.executor);
+    assert_eq!(record.amount, 10);
+    assert_eq!(record.auth, AuthPath::Fee);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (
+                    symbol_short!("fee"),
+                    symbol_short!("paid"),
+                    setup.executor.clone()
+                )
+                    .into_val(&env),
+                (token.address.clone(), 10_i128, 20_i128).into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_daily_cap() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    set_fee_config(
+        &env,
+        &setup.client,
+        Some(fee_config(&token.address, 30, 50)),
+    );
+
+    //the second fee is cut to what is left of the cap, the third is not paid
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 50));
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 60));
+    assert_eq!(token.balance(&setup.executor), 50);
+    assert_eq!(setup.client.get_fee_spending().spent, 50);
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += DAY_IN_LEDGERS);
+    assert_eq!(setup.client.get_fee_spending().spent, 0);
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 70));
+    assert_eq!(token.balance(&setup.executor), 80);
+}
+
+#[test]
+fn test_no_reimbursement_without_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    set_fee_config(
+        &env,
+        &setup.client,
+        Some(fee_config(&token.address, 10, 100)),
+    );
+
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 50));
+    assert_eq!(setup.client.get_allowance(), 50);
+    assert_eq!(token.balance(&setup.executor), 0);
+    assert_eq!(setup.client.get_fee_spending().spent, 0);
+}
+
+#[test]
+fn test_no_reimbursement_for_failed_attempt() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    set_fee_config(
+        &env,
+        &setup.client,
+        Some(fee_config(&token.address, 10, 100)),
+    );
+
+    assert!(!try_set_allowance(&env, &setup.client, &WRONG, 50));
+    assert_eq!(token.balance(&setup.executor), 10);
+}
+
+#[test]
+fn test_disable_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    set_fee_config(
+        &env,
+        &setup.client,
+        Some(fee_config(&token.address, 10, 100)),
+    );
+
+    set_fee_config(&env, &setup.client, None);
+    assert_eq!(setup.client.get_fee_config(), None);
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 50));
+    assert_eq!(token.balance(&setup.executor), 10);
+}
+
+#[test]
+fn test_set_fee_config_addr() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+
+    let config = fee_config(&token.address, 5, 25);
+    setup
+        .client
+        .set_fee_config_addr(&setup.signers, &Some(config.clone()));
+    assert_eq!(setup.client.get_fee_config(), Some(config));
+}
+
+#[test]
+#[should_panic(expected = "Fee amounts cannot be negative")]
+fn test_negative_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+
+    setup
+        .client
+        .set_fee_config_addr(&setup.signers, &Some(fee_config(&token.address, -5, 25)));
+}
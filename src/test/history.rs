@@ -0,0 +1,221 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, fund_account,
+    sign_pkey, PRIMARY,
+};
+use crate::data::{
+    AuthPath, Memo, TransactionDirection, TransactionRecord, MAX_TRANSACTION_HISTORY,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    vec, Address, BytesN, Env, IntoVal, String,
+};
+
+#[test]
+fn test_transactions_are_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let depositor = fund_account(&env, &setup.client, &token_admin, 500);
+    let recipient = Address::generate(&env);
+
+    setup.client.send_auth_addr(
+        &setup.signers,
+        &recipient,
+        &token.address,
+        &200,
+        &Memo::None,
+    );
+
+    let transactions = setup.client.get_transactions(&0, &10);
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(
+        transactions.get(0).unwrap(),
+        TransactionRecord {
+            id: 2,
+            direction: TransactionDirection::Sent,
+            token_id: token.address.clone(),
+            counterparty: recipient,
+            amount: 200,
+            ledger: env.ledger().sequence(),
+            auth: AuthPath::Owner,
+            memo: Memo::None,
+        }
+    );
+    assert_eq!(
+        transactions.get(1).unwrap(),
+        TransactionRecord {
+            id: 1,
+            direction: TransactionDirection::Received,
+            token_id: token.address.clone(),
+            counterparty: depositor,
+            amount: 500,
+            ledger: env.ledger().sequence(),
+            auth: AuthPath::Depositor,
+            memo: Memo::None,
+        }
+    );
+
+    //offset skips the newest transactions
+    let older = setup.client.get_transactions(&1, &10);
+    assert_eq!(older.len(), 1);
+    assert_eq!(older.get(0).unwrap().id, 1);
+    assert_eq!(setup.client.get_transactions(&2, &10).len(), 0);
+}
+
+#[test]
+fn test_pkey_send_is_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let recipient = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "send_with_pkey",
+        (
+            recipient.clone(),
+            token.address.clone(),
+            200_i128,
+            Memo::None,
+        ),
+    );
+    setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &200,
+        &Memo::None,
+    );
+
+    let record = setup.client.get_transactions(&0, &1).get(0).unwrap();
+    assert_eq!(record.direction, TransactionDirection::Sent);
+    assert_eq!(record.counterparty, recipient);
+    assert_eq!(record.auth, AuthPath::Pkey);
+}
+
+#[test]
+fn test_history_keeps_latest_transactions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (_, token_admin) = create_token(&env);
+    env.budget().reset_unlimited();
+
+    for _ in 0..MAX_TRANSACTION_HISTORY + 5 {
+        fund_account(&env, &setup.client, &token_admin, 10);
+    }
+
+    let transactions = setup
+        .client
+        .get_transactions(&0, &(MAX_TRANSACTION_HISTORY + 5));
+    assert_eq!(transactions.len(), MAX_TRANSACTION_HISTORY);
+    assert_eq!(transactions.get(0).unwrap().id, MAX_TRANSACTION_HISTORY + 5);
+    assert_eq!(transactions.get(MAX_TRANSACTION_HISTORY - 1).unwrap().id, 6);
+    assert_eq!(setup.client.get_tx_count(), MAX_TRANSACTION_HISTORY + 5);
+}
+
+#[test]
+fn test_memos_are_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let depositor = Address::generate(&env);
+    token_admin.mint(&depositor, &500);
+    let recipient = Address::generate(&env);
+
+    let reference = Memo::Hash(BytesN::from_array(&env, &[7; 32]));
+    setup
+        .client
+        .receive(&depositor, &token.address, &500, &reference);
+    let invoice = Memo::Text(String::from_str(&env, "invoice 42"));
+    setup
+        .client
+        .send_auth_addr(&setup.signers, &recipient, &token.address, &200, &invoice);
+
+    //the send quest award is published after the transaction
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 2..events.len() - 1),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (
+                    symbol_short!("tx"),
+                    TransactionDirection::Sent,
+                    recipient.clone()
+                )
+                    .into_val(&env),
+                (2_u32, token.address.clone(), 200_i128, invoice.clone()).into_val(&env),
+            )
+        ]
+    );
+
+    let transactions = setup.client.get_transactions(&0, &2);
+    assert_eq!(transactions.get(0).unwrap().memo, invoice);
+    assert_eq!(transactions.get(1).unwrap().memo, reference);
+}
+
+#[test]
+#[should_panic(expected = "Memo is too long")]
+fn test_memo_too_long() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let memo = Memo::Text(String::from_str(
+        &env,
+        "a memo that is much longer than the sixty four bytes allowed for it",
+    ));
+    setup.client.send_auth_addr(
+        &setup.signers,
+        &Address::generate(&env),
+        &token.address,
+        &200,
+        &memo,
+    );
+}
+
+#[test]
+fn test_pkey_proof_covers_memo() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let recipient = Address::generate(&env);
+
+    let signed = Memo::Text(String::from_str(&env, "invoice 42"));
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "send_with_pkey",
+        (recipient.clone(), token.address.clone(), 200_i128, signed),
+    );
+    let sent = setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &200,
+        &Memo::Text(String::from_str(&env, "invoice 43")),
+    );
+
+    assert!(!sent);
+    assert_eq!(token.balance(&recipient), 0);
+}
@@ -0,0 +1,228 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, env_with_long_ttl,
+    fund_account, sign_pkey, PRIMARY,
+};
+use crate::data::{Beneficiary, InheritanceConfig, Memo, ProposalAction, DAY_IN_LEDGERS};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+
+fn inheritance_config(env: &Env, first: &Address, second: &Address) -> InheritanceConfig {
+    InheritanceConfig {
+        beneficiaries: vec![
+            env,
+            Beneficiary {
+                address: first.clone(),
+                share_bps: 6_000,
+            },
+            Beneficiary {
+                address: second.clone(),
+                share_bps: 4_000,
+            },
+        ],
+        inactivity_days: 30,
+        grace_days: 10,
+    }
+}
+
+fn advance_days(env: &Env, days: u32) {
+    env.ledger()
+        .with_mut(|li| li.sequence_number += days * DAY_IN_LEDGERS);
+}
+
+#[test]
+fn test_claim_after_inactivity() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 501);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let config = inheritance_config(&env, &first, &second);
+    setup
+        .client
+        .set_inheritance_addr(&setup.signers, &Some(config.clone()));
+    assert_eq!(setup.client.get_inheritance(), Some(config));
+    assert_eq!(setup.client.get_last_activity(), env.ledger().sequence());
+
+    advance_days(&env, 40);
+    assert_eq!(setup.client.claim_inheritance(&second, &token.address), 501);
+
+    //the last beneficiary gets the rounding remainder
+    assert_eq!(token.balance(&first), 300);
+    assert_eq!(token.balance(&second), 201);
+    assert_eq!(setup.client.get_balance(&token.address), 0);
+}
+
+#[test]
+fn test_claim_releases_vaults() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let lock_until = env.ledger().sequence() + 365 * DAY_IN_LEDGERS;
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "savings"),
+        &token.address,
+        &0,
+        &lock_until,
+    );
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &300);
+    setup.client.set_inheritance_addr(
+        &setup.signers,
+        &Some(inheritance_config(&env, &first, &second)),
+    );
+
+    //locked vault funds are paid out too
+    advance_days(&env, 40);
+    assert_eq!(setup.client.claim_inheritance(&first, &token.address), 500);
+    assert_eq!(token.balance(&first), 300);
+    assert_eq!(token.balance(&second), 200);
+    assert_eq!(setup.client.get_vault(&vault_id).unwrap().balance, 0);
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Account is still active")]
+fn test_claim_during_grace_period() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let first = Address::generate(&env);
+    let config = inheritance_config(&env, &first, &Address::generate(&env));
+    setup
+        .client
+        .set_inheritance_addr(&setup.signers, &Some(config));
+
+    advance_days(&env, 39);
+    setup.client.claim_inheritance(&first, &token.address);
+}
+
+#[test]
+#[should_panic(expected = "Account is still active")]
+fn test_activity_cancels_claim() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let first = Address::generate(&env);
+    let config = inheritance_config(&env, &first, &Address::generate(&env));
+    setup
+        .client
+        .set_inheritance_addr(&setup.signers, &Some(config));
+
+    advance_days(&env, 35);
+    setup.client.send_auth_addr(
+        &setup.signers,
+        &Address::generate(&env),
+        &token.address,
+        &100,
+        &Memo::None,
+    );
+    assert_eq!(setup.client.get_last_activity(), env.ledger().sequence());
+
+    advance_days(&env, 10);
+    setup.client.claim_inheritance(&first, &token.address);
+}
+
+#[test]
+fn test_pkey_action_records_activity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+
+    let auth = sign_pkey(&env, &setup.client, &PRIMARY, "revoke_all_pkey", ());
+    assert!(setup
+        .client
+        .revoke_all_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof));
+    assert_eq!(setup.client.get_last_activity(), env.ledger().sequence());
+}
+
+#[test]
+fn test_proposals_record_activity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+    let signer = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+    let action = ProposalAction::Transfer(Address::generate(&env), token.address, 100);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    assert_eq!(setup.client.get_last_activity(), env.ledger().sequence());
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+    setup.client.approve_proposal(&signer, &proposal_id);
+    assert_eq!(setup.client.get_last_activity(), env.ledger().sequence());
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+    setup.client.revoke_proposal(&setup.owner, &proposal_id);
+    assert_eq!(setup.client.get_last_activity(), env.ledger().sequence());
+}
+
+#[test]
+#[should_panic(expected = "Address is not a beneficiary")]
+fn test_claim_by_non_beneficiary() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let config = inheritance_config(&env, &Address::generate(&env), &Address::generate(&env));
+    setup
+        .client
+        .set_inheritance_addr(&setup.signers, &Some(config));
+
+    advance_days(&env, 40);
+    setup
+        .client
+        .claim_inheritance(&Address::generate(&env), &token.address);
+}
+
+#[test]
+#[should_panic(expected = "Beneficiary shares must add up to 10000")]
+fn test_shares_must_add_up() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let mut config = inheritance_config(&env, &Address::generate(&env), &Address::generate(&env));
+    config.beneficiaries.set(
+        1,
+        Beneficiary {
+            address: Address::generate(&env),
+            share_bps: 3_000,
+        },
+    );
+
+    setup
+        .client
+        .set_inheritance_addr(&setup.signers, &Some(config));
+}
+
+#[test]
+#[should_panic(expected = "Inactivity period must be at least 30 days")]
+fn test_minimum_inactivity_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let mut config = inheritance_config(&env, &Address::generate(&env), &Address::generate(&env));
+    config.inactivity_days = 0;
+    config.grace_days = 0;
+
+    setup
+        .client
+        .set_inheritance_addr(&setup.signers, &Some(config));
+}
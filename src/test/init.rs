@@ -0,0 +1,81 @@
+use super::{
+    create_account_with_address, create_account_with_profile, MAX_ALLOWANCE, PLATFORM, PROFILE_ID,
+};
+use crate::data::Signer;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+#[test]
+fn test_init_with_address() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+
+    assert_eq!(
+        setup.client.get_signers(),
+        vec![
+            &env,
+            Signer {
+                address: setup.owner.clone(),
+                weight: 1,
+            }
+        ]
+    );
+    assert_eq!(setup.client.get_allowance(), 0);
+    assert_eq!(setup.client.get_tx_count(), 0);
+    assert_eq!(setup.client.get_tokens().len(), 0);
+}
+
+#[test]
+fn test_init_with_profile() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+
+    assert_eq!(setup.client.get_allowance(), MAX_ALLOWANCE);
+    assert!(setup.client.get_signers().is_empty());
+    assert_eq!(
+        setup
+            .client
+            .get_nonce(&PLATFORM, &String::from_str(&env, PROFILE_ID)),
+        0
+    );
+
+    let keys = setup
+        .client
+        .get_encrypted_keys(&PLATFORM, &String::from_str(&env, PROFILE_ID));
+    assert_eq!(keys.gen_salt_encrypted, String::from_str(&env, "salt"));
+    assert_eq!(keys.salt_encryption_iv, String::from_str(&env, "salt_iv"));
+    assert_eq!(keys.index_encrypted, String::from_str(&env, "key_index"));
+    assert_eq!(keys.index_encryption_iv, String::from_str(&env, "index_iv"));
+}
+
+#[test]
+#[should_panic(expected = "Profile is not linked to this account")]
+fn test_encrypted_keys_wrong_profile() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+
+    setup
+        .client
+        .get_encrypted_keys(&PLATFORM, &String::from_str(&env, "someone_else"));
+}
+
+#[test]
+#[should_panic(expected = "has already been initilized")]
+fn test_init_with_address_twice() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+
+    setup
+        .client
+        .init_with_address(&setup.controller, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "has already been initilized")]
+fn test_init_with_address_after_profile() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+
+    setup
+        .client
+        .init_with_address(&setup.controller, &Address::generate(&env));
+}
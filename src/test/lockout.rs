@@ -0,0 +1,161 @@
+use super::{
+    create_account_with_profile, sign_pkey, try_set_allowance, PLATFORM, PRIMARY, PROFILE_ID, WRONG,
+};
+use crate::{
+    data::{FailedAttempts, AUTH_LOCKOUT_BASE},
+    SubAccountClient,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, IntoVal, String,
+};
+
+fn fail_attempts(env: &Env, client: &SubAccountClient, times: u32) {
+    for _ in 0..times {
+        assert!(!try_set_allowance(env, client, &WRONG, 50));
+    }
+}
+
+fn advance_ledger(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_failed_attempt_is_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let auth = sign_pkey(&env, &setup.client, &WRONG, "set_allowance_pkey", 50_i128);
+    assert!(!setup.client.set_allowance_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &50
+    ));
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (
+                    symbol_short!("auth"),
+                    symbol_short!("failed"),
+                    setup.executor.clone()
+                )
+                    .into_val(&env),
+                (1_u32, 0_u32).into_val(&env),
+            )
+        ]
+    );
+
+    let expected = FailedAttempts {
+        count: 1,
+        total: 1,
+        last_failure_ledger: env.ledger().sequence(),
+        locked_until: 0,
+    };
+    assert_eq!(setup.client.get_failed_attempts(), expected);
+    assert_eq!(
+        setup.client.get_executor_failed_attempts(&setup.executor),
+        expected
+    );
+}
+
+#[test]
+#[should_panic(expected = "Too many failed passkey attempts, try again later")]
+fn test_lockout_after_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    fail_attempts(&env, &setup.client, 3);
+    assert_eq!(
+        setup.client.get_failed_attempts().locked_until,
+        env.ledger().sequence() + AUTH_LOCKOUT_BASE
+    );
+
+    //even a valid proof is refused while locked out
+    let auth = sign_pkey(&env, &setup.client, &PRIMARY, "set_allowance_pkey", 50_i128);
+    setup
+        .client
+        .set_allowance_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &50);
+}
+
+#[test]
+fn test_lockout_backoff_and_reset() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    fail_attempts(&env, &setup.client, 3);
+    advance_ledger(&env, AUTH_LOCKOUT_BASE);
+
+    //every further failure doubles the lockout
+    fail_attempts(&env, &setup.client, 1);
+    assert_eq!(
+        setup.client.get_failed_attempts().locked_until,
+        env.ledger().sequence() + 2 * AUTH_LOCKOUT_BASE
+    );
+    advance_ledger(&env, 2 * AUTH_LOCKOUT_BASE);
+    fail_attempts(&env, &setup.client, 1);
+    assert_eq!(
+        setup.client.get_failed_attempts().locked_until,
+        env.ledger().sequence() + 4 * AUTH_LOCKOUT_BASE
+    );
+
+    //a successful verification after the lockout resets the count, the total is kept
+    advance_ledger(&env, 4 * AUTH_LOCKOUT_BASE);
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 50));
+    let attempts = setup.client.get_failed_attempts();
+    assert_eq!(attempts.count, 0);
+    assert_eq!(attempts.total, 5);
+    assert_eq!(
+        setup
+            .client
+            .get_executor_failed_attempts(&setup.executor)
+            .count,
+        0
+    );
+    assert_eq!(setup.client.get_allowance(), 50);
+}
+
+#[test]
+fn test_lockout_per_executor_and_account() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, crate::SubAccount);
+    let client = SubAccountClient::new(&env, &contract_id);
+    let value = String::from_str(&env, "encrypted");
+    client.init_with_profile(
+        &Address::generate(&env),
+        &PLATFORM,
+        &String::from_str(&env, PROFILE_ID),
+        &value,
+        &value,
+        &value,
+        &value,
+        &PRIMARY.passkey(&env),
+        &1_000,
+    );
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.set_executor(&1, &first);
+    client.set_executor(&2, &second);
+    client.set_executor_done();
+
+    fail_attempts(&env, &client, 3);
+    assert_eq!(client.get_executor_failed_attempts(&first).count, 3);
+    assert_eq!(client.get_executor_failed_attempts(&second).count, 0);
+
+    //the account wide lockout also blocks the other executor
+    let auth = sign_pkey(&env, &client, &PRIMARY, "set_allowance_pkey", 50_i128);
+    assert!(client
+        .try_set_allowance_pkey(&2, &auth.nonce, &auth.expiration_ledger, &auth.proof, &50)
+        .is_err());
+}
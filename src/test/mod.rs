@@ -0,0 +1,258 @@
+#![cfg(test)]
+extern crate std;
+
+mod allowance;
+mod approvals;
+mod children;
+mod credentials;
+mod executors;
+mod fees;
+mod history;
+mod inheritance;
+mod init;
+mod lockout;
+mod nonce;
+mod owner;
+mod profiles;
+mod proposals;
+mod quest;
+mod requests;
+mod rewards;
+mod signers;
+mod smart_transact;
+mod streams;
+mod tokens;
+mod transfers;
+mod upgrade;
+mod vaults;
+
+use crate::{
+    access::{pkey_action, pkey_payload},
+    data::{Memo, DAY_IN_LEDGERS},
+    types::Platform,
+    SubAccount, SubAccountClient,
+};
+use k256::ecdsa::SigningKey;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, IntoVal, String, Val, Vec,
+};
+
+pub(crate) const PASSKEY_SECRET: [u8; 32] = [1; 32];
+pub(crate) const WRONG_PASSKEY_SECRET: [u8; 32] = [2; 32];
+pub(crate) const PLATFORM: Platform = Platform::X;
+pub(crate) const PROFILE_ID: &str = "1234567890";
+pub(crate) const MAX_ALLOWANCE: i128 = 1_000;
+pub(crate) const PKEY_VALIDITY: u32 = 100;
+
+//A linked credential able to produce passkey proofs
+pub(crate) struct Credential {
+    pub platform: Platform,
+    pub profile_id: &'static str,
+    pub secret_key: [u8; 32],
+}
+
+impl Credential {
+    fn signing_key(&self) -> SigningKey {
+        SigningKey::from_bytes(&self.secret_key.into()).unwrap()
+    }
+
+    //Uncompressed public key the account stores for the profile
+    pub fn passkey(&self, env: &Env) -> BytesN<65> {
+        let point = self.signing_key().verifying_key().to_encoded_point(false);
+        BytesN::from_array(env, point.as_bytes().try_into().unwrap())
+    }
+}
+
+pub(crate) const PRIMARY: Credential = Credential {
+    platform: PLATFORM,
+    profile_id: PROFILE_ID,
+    secret_key: PASSKEY_SECRET,
+};
+
+pub(crate) const WRONG: Credential = Credential {
+    platform: PLATFORM,
+    profile_id: PROFILE_ID,
+    secret_key: WRONG_PASSKEY_SECRET,
+};
+
+pub(crate) struct PkeyAuth {
+    pub nonce: u32,
+    pub expiration_ledger: u32,
+    pub proof: BytesN<65>,
+}
+
+pub(crate) struct AddressSetup<'a> {
+    pub client: SubAccountClient<'a>,
+    pub controller: Address,
+    pub owner: Address,
+    pub signers: Vec<Address>,
+}
+
+pub(crate) struct ProfileSetup<'a> {
+    pub client: SubAccountClient<'a>,
+    pub controller: Address,
+    pub executor: Address,
+}
+
+//Keeps the contract instance alive when tests advance the ledger by days
+pub(crate) fn env_with_long_ttl() -> Env {
+    let env = Env::default();
+    env.ledger().with_mut(|li| {
+        li.min_persistent_entry_ttl = 100 * DAY_IN_LEDGERS;
+        li.max_entry_ttl = 200 * DAY_IN_LEDGERS;
+    });
+    env
+}
+
+pub(crate) fn passkey(env: &Env) -> BytesN<65> {
+    PRIMARY.passkey(env)
+}
+
+//Signs the proof a client would produce for the credential's current nonce
+pub(crate) fn sign_pkey(
+    env: &Env,
+    client: &SubAccountClient,
+    credential: &Credential,
+    function: &str,
+    args: impl IntoVal<Env, Val>,
+) -> PkeyAuth {
+    let nonce = client.get_nonce(
+        &credential.platform,
+        &String::from_str(env, credential.profile_id),
+    );
+    let expiration_ledger = env.ledger().sequence() + PKEY_VALIDITY;
+    sign_pkey_with(
+        env,
+        client,
+        credential,
+        nonce,
+        expiration_ledger,
+        function,
+        args,
+    )
+}
+
+//Sets the allowance with a proof from the credential, returns whether the call succeeded
+pub(crate) fn try_set_allowance(
+    env: &Env,
+    client: &SubAccountClient,
+    credential: &Credential,
+    allowance: i128,
+) -> bool {
+    let auth = sign_pkey(env, client, credential, "set_allowance_pkey", allowance);
+    client
+        .try_set_allowance_pkey(
+            &1,
+            &auth.nonce,
+            &auth.expiration_ledger,
+            &auth.proof,
+            &allowance,
+        )
+        .is_ok_and(|result| result.unwrap())
+}
+
+pub(crate) fn set_owner_pkey(env: &Env, client: &SubAccountClient, owner: &Address) {
+    let auth = sign_pkey(env, client, &PRIMARY, "set_owner_pkey", owner.clone());
+    client.set_owner_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, owner);
+}
+
+pub(crate) fn sign_pkey_with(
+    env: &Env,
+    client: &SubAccountClient,
+    credential: &Credential,
+    nonce: u32,
+    expiration_ledger: u32,
+    function: &str,
+    args: impl IntoVal<Env, Val>,
+) -> PkeyAuth {
+    let digest = env.as_contract(&client.address, || {
+        let action = pkey_action(env, function, args);
+        let payload = pkey_payload(env, nonce, expiration_ledger, &action);
+        env.crypto().sha256(&payload).to_array()
+    });
+    let (signature, recovery_id) = credential
+        .signing_key()
+        .sign_prehash_recoverable(&digest)
+        .unwrap();
+    let mut proof = [0u8; 65];
+    proof[..64].copy_from_slice(&signature.to_bytes());
+    proof[64] = recovery_id.to_byte();
+    let proof = BytesN::from_array(env, &proof);
+    PkeyAuth {
+        nonce,
+        expiration_ledger,
+        proof,
+    }
+}
+
+//Sets a single executor at index 1 and locks the executor set, as done on account creation
+fn set_executors(env: &Env, client: &SubAccountClient) -> Address {
+    let executor = Address::generate(env);
+    client.set_executor(&1, &executor);
+    client.set_executor_done();
+    executor
+}
+
+pub(crate) fn create_account_with_address(env: &Env) -> AddressSetup<'_> {
+    let contract_id = env.register_contract(None, SubAccount);
+    let client = SubAccountClient::new(env, &contract_id);
+    let controller = Address::generate(env);
+    let owner = Address::generate(env);
+    client.init_with_address(&controller, &owner);
+    set_executors(env, &client);
+
+    AddressSetup {
+        client,
+        controller,
+        signers: vec![env, owner.clone()],
+        owner,
+    }
+}
+
+pub(crate) fn create_account_with_profile(env: &Env) -> ProfileSetup<'_> {
+    let contract_id = env.register_contract(None, SubAccount);
+    let client = SubAccountClient::new(env, &contract_id);
+    let controller = Address::generate(env);
+    client.init_with_profile(
+        &controller,
+        &PLATFORM,
+        &String::from_str(env, PROFILE_ID),
+        &String::from_str(env, "salt"),
+        &String::from_str(env, "salt_iv"),
+        &String::from_str(env, "key_index"),
+        &String::from_str(env, "index_iv"),
+        &passkey(env),
+        &MAX_ALLOWANCE,
+    );
+    let executor = set_executors(env, &client);
+
+    ProfileSetup {
+        client,
+        controller,
+        executor,
+    }
+}
+
+pub(crate) fn create_token<'a>(env: &Env) -> (TokenClient<'a>, StellarAssetClient<'a>) {
+    let admin = Address::generate(env);
+    let token_id = env.register_stellar_asset_contract_v2(admin).address();
+    (
+        TokenClient::new(env, &token_id),
+        StellarAssetClient::new(env, &token_id),
+    )
+}
+
+//Mints to a depositor and receives the funds into the sub-account
+pub(crate) fn fund_account(
+    env: &Env,
+    client: &SubAccountClient,
+    token_admin: &StellarAssetClient,
+    amount: i128,
+) -> Address {
+    let depositor = Address::generate(env);
+    token_admin.mint(&depositor, &amount);
+    client.receive(&depositor, &token_admin.address, &amount, &Memo::None);
+    depositor
+}
@@ -0,0 +1,263 @@
+use super::{
+    create_account_with_profile, create_token, sign_pkey, sign_pkey_with, PLATFORM, PRIMARY,
+    PROFILE_ID, WRONG,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+#[test]
+fn test_create_tx_nonce() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    let spender = Address::generate(&env);
+    let profile_id = String::from_str(&env, PROFILE_ID);
+
+    assert_eq!(setup.client.get_nonce(&PLATFORM, &profile_id), 0);
+    assert!(setup.client.get_tx_nonce().is_empty());
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_tx_nonce",
+        (spender.clone(), token.address.clone(), 100_i128),
+    );
+    setup.client.create_tx_nonce(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &spender,
+        &token.address,
+        &100,
+    );
+    let first = setup.client.get_tx_nonce();
+    assert_eq!(setup.client.get_nonce(&PLATFORM, &profile_id), 1);
+    assert!(!first.is_empty());
+
+    //same request with a new sequence number gives a different nonce
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_tx_nonce",
+        (spender.clone(), token.address.clone(), 100_i128),
+    );
+    setup.client.create_tx_nonce(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &spender,
+        &token.address,
+        &100,
+    );
+    let second = setup.client.get_tx_nonce();
+    assert_eq!(setup.client.get_nonce(&PLATFORM, &profile_id), 2);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_clear_tx_nonce() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    let spender = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_tx_nonce",
+        (spender.clone(), token.address.clone(), 100_i128),
+    );
+    setup.client.create_tx_nonce(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &spender,
+        &token.address,
+        &100,
+    );
+    setup.client.clear_tx_nonce();
+
+    assert!(setup.client.get_tx_nonce().is_empty());
+    assert_eq!(
+        setup
+            .client
+            .get_nonce(&PLATFORM, &String::from_str(&env, PROFILE_ID)),
+        1
+    );
+}
+
+#[test]
+fn test_create_tx_nonce_wrong_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    let spender = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &WRONG,
+        "create_tx_nonce",
+        (spender.clone(), token.address.clone(), 100_i128),
+    );
+    assert!(!setup.client.create_tx_nonce(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &spender,
+        &token.address,
+        &100,
+    ));
+    assert!(setup.client.get_tx_nonce().is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_create_tx_nonce_without_executor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+    let spender = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_tx_nonce",
+        (spender.clone(), token.address.clone(), 100_i128),
+    );
+    setup.client.create_tx_nonce(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &spender,
+        &token.address,
+        &100,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid nonce")]
+fn test_pkey_replay_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_allowance_pkey",
+        250_i128,
+    );
+    setup
+        .client
+        .set_allowance_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &250);
+    setup
+        .client
+        .set_allowance_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &250);
+}
+
+#[test]
+#[should_panic(expected = "Invalid nonce")]
+fn test_pkey_future_nonce_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let auth = sign_pkey_with(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        5,
+        env.ledger().sequence() + 100,
+        "set_allowance_pkey",
+        250_i128,
+    );
+    setup
+        .client
+        .set_allowance_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &250);
+}
+
+#[test]
+#[should_panic(expected = "Passkey authorization has expired")]
+fn test_pkey_expired_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_allowance_pkey",
+        250_i128,
+    );
+    env.ledger()
+        .with_mut(|li| li.sequence_number = auth.expiration_ledger + 1);
+    setup
+        .client
+        .set_allowance_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &250);
+}
+
+#[test]
+fn test_pkey_proof_bound_to_args_and_function() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    //a proof signed for one allowance cannot set another
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_allowance_pkey",
+        250_i128,
+    );
+    assert!(!setup.client.set_allowance_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &900
+    ));
+
+    //a proof signed with a different expiry than submitted is rejected
+    assert!(!setup.client.set_allowance_pkey(
+        &1,
+        &auth.nonce,
+        &(auth.expiration_ledger + 1),
+        &auth.proof,
+        &250,
+    ));
+
+    //a proof signed for another entry point is rejected
+    let owner = Address::generate(&env);
+    assert!(!setup.client.set_owner_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &owner,
+    ));
+
+    assert_eq!(
+        setup
+            .client
+            .get_nonce(&PLATFORM, &String::from_str(&env, PROFILE_ID)),
+        0
+    );
+}
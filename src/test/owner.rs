@@ -0,0 +1,172 @@
+use super::{create_account_with_address, create_account_with_profile, env_with_long_ttl};
+use crate::data::{PendingOwner, Signer, OWNER_TRANSFER_EXPIRY};
+extern crate std;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger},
+    vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+#[test]
+fn test_propose_and_accept_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let new_owner = Address::generate(&env);
+
+    setup.client.propose_owner(&setup.signers, &new_owner);
+    let expiration_ledger = env.ledger().sequence() + OWNER_TRANSFER_EXPIRY;
+    assert_eq!(
+        setup.client.get_pending_owner(),
+        Some(PendingOwner {
+            new_owner: new_owner.clone(),
+            expiration_ledger,
+        })
+    );
+    //ownership only moves once accepted
+    assert_eq!(setup.client.get_owner(), setup.owner);
+
+    setup.client.accept_owner();
+    assert_eq!(setup.client.get_owner(), new_owner);
+    assert_eq!(
+        setup.client.get_signers(),
+        vec![
+            &env,
+            Signer {
+                address: new_owner.clone(),
+                weight: 1,
+            }
+        ]
+    );
+    assert_eq!(setup.client.get_pending_owner(), None);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (symbol_short!("owner"), symbol_short!("accepted")).into_val(&env),
+                new_owner.into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_accept_owner_requires_new_owner_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let new_owner = Address::generate(&env);
+    setup.client.propose_owner(&setup.signers, &new_owner);
+
+    env.set_auths(&[]);
+    assert!(setup.client.try_accept_owner().is_err());
+
+    env.mock_all_auths();
+    setup.client.accept_owner();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            new_owner.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    setup.client.address.clone(),
+                    Symbol::new(&env, "accept_owner"),
+                    vec![&env],
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_propose_owner_without_owner_auth() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+
+    setup
+        .client
+        .propose_owner(&Vec::new(&env), &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Owner transfer has expired")]
+fn test_accept_owner_after_expiry() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    setup
+        .client
+        .propose_owner(&setup.signers, &Address::generate(&env));
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += OWNER_TRANSFER_EXPIRY + 1);
+    setup.client.accept_owner();
+}
+
+#[test]
+#[should_panic(expected = "No pending owner transfer")]
+fn test_cancel_owner_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let new_owner = Address::generate(&env);
+    setup.client.propose_owner(&setup.signers, &new_owner);
+
+    setup.client.cancel_owner_transfer(&setup.signers);
+    assert_eq!(setup.client.get_pending_owner(), None);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (symbol_short!("owner"), symbol_short!("cancelled")).into_val(&env),
+                new_owner.into_val(&env),
+            )
+        ]
+    );
+
+    setup.client.accept_owner();
+}
+
+#[test]
+#[should_panic(expected = "Owner not set")]
+fn test_propose_owner_without_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    setup
+        .client
+        .propose_owner(&Vec::new(&env), &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Address is already the owner")]
+fn test_propose_current_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+
+    setup.client.propose_owner(&setup.signers, &setup.owner);
+}
+
+#[test]
+#[should_panic(expected = "Owner not found!")]
+fn test_get_owner_with_several_signers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    setup
+        .client
+        .add_signer(&setup.signers, &Address::generate(&env), &1);
+
+    setup.client.get_owner();
+}
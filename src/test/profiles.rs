@@ -0,0 +1,345 @@
+use super::{
+    create_account_with_address, create_account_with_profile, passkey, set_owner_pkey, sign_pkey,
+    try_set_allowance, Credential, PLATFORM, PRIMARY, PROFILE_ID, WRONG,
+};
+use crate::{
+    data::{LinkedProfile, ProfileEncrypted},
+    types::Platform,
+    SubAccountClient,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+const DISCORD_ID: &str = "80351110224678912";
+const DISCORD_PASSKEY_SECRET: [u8; 32] = [3; 32];
+
+const DISCORD: Credential = Credential {
+    platform: Platform::Discord,
+    profile_id: DISCORD_ID,
+    secret_key: DISCORD_PASSKEY_SECRET,
+};
+
+fn encrypted_profile(env: &Env, platform: Platform, profile_id: &str) -> ProfileEncrypted {
+    ProfileEncrypted {
+        platform,
+        profile_id: String::from_str(env, profile_id),
+        gen_salt_encrypted: String::from_str(env, "discord_salt"),
+        salt_encryption_iv: String::from_str(env, "discord_salt_iv"),
+        index_encrypted: String::from_str(env, "discord_key_index"),
+        index_encryption_iv: String::from_str(env, "discord_index_iv"),
+    }
+}
+
+fn try_link_pkey(
+    env: &Env,
+    client: &SubAccountClient,
+    credential: &Credential,
+    profile: ProfileEncrypted,
+) -> bool {
+    let passkey = DISCORD.passkey(env);
+    let auth = sign_pkey(
+        env,
+        client,
+        credential,
+        "link_profile_pkey",
+        (profile.clone(), passkey.clone()),
+    );
+    client
+        .try_link_profile_pkey(
+            &1,
+            &auth.nonce,
+            &auth.expiration_ledger,
+            &auth.proof,
+            &profile,
+            &passkey,
+        )
+        .is_ok_and(|result| result.unwrap())
+}
+
+fn link_discord_pkey(env: &Env, client: &SubAccountClient, credential: &Credential) -> bool {
+    let profile = encrypted_profile(env, Platform::Discord, DISCORD_ID);
+    let passkey = DISCORD.passkey(env);
+    let auth = sign_pkey(
+        env,
+        client,
+        credential,
+        "link_profile_pkey",
+        (profile.clone(), passkey.clone()),
+    );
+    client.link_profile_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &profile,
+        &passkey,
+    )
+}
+
+fn unlink_pkey(
+    env: &Env,
+    client: &SubAccountClient,
+    credential: &Credential,
+    platform: Platform,
+    profile_id: &str,
+) {
+    let profile_id = String::from_str(env, profile_id);
+    let auth = sign_pkey(
+        env,
+        client,
+        credential,
+        "unlink_profile_pkey",
+        (platform, profile_id.clone()),
+    );
+    client.unlink_profile_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &platform,
+        &profile_id,
+    );
+}
+
+#[test]
+fn test_link_profile_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    assert!(link_discord_pkey(&env, &setup.client, &PRIMARY));
+
+    assert_eq!(
+        setup.client.get_linked_profiles(),
+        vec![
+            &env,
+            LinkedProfile {
+                platform: PLATFORM,
+                profile_id: String::from_str(&env, PROFILE_ID),
+            },
+            LinkedProfile {
+                platform: Platform::Discord,
+                profile_id: String::from_str(&env, DISCORD_ID),
+            },
+        ]
+    );
+    let keys = setup
+        .client
+        .get_encrypted_keys(&Platform::Discord, &String::from_str(&env, DISCORD_ID));
+    assert_eq!(
+        keys.gen_salt_encrypted,
+        String::from_str(&env, "discord_salt")
+    );
+    assert_eq!(
+        keys.index_encryption_iv,
+        String::from_str(&env, "discord_index_iv")
+    );
+
+    //the new profile passkey can authorize actions
+    assert!(try_set_allowance(&env, &setup.client, &DISCORD, 50));
+    assert_eq!(setup.client.get_allowance(), 50);
+}
+
+#[test]
+fn test_link_profile_wrong_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    assert!(!link_discord_pkey(&env, &setup.client, &WRONG));
+    assert_eq!(setup.client.get_linked_profiles().len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_link_profile_pkey_without_executor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+
+    assert!(link_discord_pkey(&env, &setup.client, &PRIMARY));
+}
+
+#[test]
+#[should_panic(expected = "Profile is already linked")]
+fn test_link_profile_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    assert!(link_discord_pkey(&env, &setup.client, &PRIMARY));
+    assert!(link_discord_pkey(&env, &setup.client, &PRIMARY));
+}
+
+#[test]
+fn test_link_profile_addr() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+
+    setup.client.link_profile_addr(
+        &setup.signers,
+        &encrypted_profile(&env, Platform::Discord, DISCORD_ID),
+        &DISCORD.passkey(&env),
+    );
+
+    assert_eq!(setup.client.get_linked_profiles().len(), 1);
+    assert!(try_set_allowance(&env, &setup.client, &DISCORD, 50));
+    assert_eq!(setup.client.get_allowance(), 50);
+}
+
+#[test]
+fn test_unlink_profile_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    assert!(link_discord_pkey(&env, &setup.client, &PRIMARY));
+
+    unlink_pkey(&env, &setup.client, &DISCORD, PLATFORM, PROFILE_ID);
+
+    assert_eq!(setup.client.get_linked_profiles().len(), 1);
+    assert!(setup
+        .client
+        .try_get_encrypted_keys(&PLATFORM, &String::from_str(&env, PROFILE_ID))
+        .is_err());
+    //the unlinked profile passkey is no longer valid
+    assert!(!try_set_allowance(&env, &setup.client, &PRIMARY, 50));
+}
+
+#[test]
+#[should_panic(expected = "Cannot remove the last credential of the account")]
+fn test_unlink_last_profile_without_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    unlink_pkey(&env, &setup.client, &PRIMARY, PLATFORM, PROFILE_ID);
+}
+
+#[test]
+fn test_unlink_last_profile_with_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let owner = Address::generate(&env);
+    set_owner_pkey(&env, &setup.client, &owner);
+
+    setup.client.unlink_profile_addr(
+        &vec![&env, owner.clone()],
+        &PLATFORM,
+        &String::from_str(&env, PROFILE_ID),
+    );
+    assert_eq!(setup.client.get_linked_profiles().len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Profile is not linked to this account")]
+fn test_unlink_unknown_profile() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    unlink_pkey(&env, &setup.client, &PRIMARY, Platform::Discord, DISCORD_ID);
+}
+
+#[test]
+fn test_nonces_are_per_credential() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let profile_id = String::from_str(&env, PROFILE_ID);
+    let discord_id = String::from_str(&env, DISCORD_ID);
+    assert!(link_discord_pkey(&env, &setup.client, &PRIMARY));
+
+    assert_eq!(setup.client.get_nonce(&PLATFORM, &profile_id), 1);
+    assert_eq!(setup.client.get_nonce(&Platform::Discord, &discord_id), 0);
+
+    assert!(try_set_allowance(&env, &setup.client, &DISCORD, 50));
+    assert!(try_set_allowance(&env, &setup.client, &DISCORD, 60));
+    assert_eq!(setup.client.get_nonce(&PLATFORM, &profile_id), 1);
+    assert_eq!(setup.client.get_nonce(&Platform::Discord, &discord_id), 2);
+}
+
+#[test]
+fn test_creation_platform() {
+    let env = Env::default();
+    let address_setup = create_account_with_address(&env);
+    let profile_setup = create_account_with_profile(&env);
+
+    assert_eq!(
+        address_setup.client.get_creation_platform(),
+        Platform::Stellar
+    );
+    assert_eq!(profile_setup.client.get_creation_platform(), PLATFORM);
+}
+
+fn try_link_profile(env: &Env, platform: Platform, profile_id: &str) -> bool {
+    let setup = create_account_with_profile(env);
+    try_link_pkey(
+        env,
+        &setup.client,
+        &PRIMARY,
+        encrypted_profile(env, platform, profile_id),
+    )
+}
+
+#[test]
+fn test_profile_id_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let valid = [
+        (
+            Platform::Stellar,
+            "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H",
+        ),
+        (Platform::Email, "user@socket.fi"),
+        (Platform::X, "44196397"),
+        (Platform::Discord, DISCORD_ID),
+        (Platform::Telegram, "123456789"),
+    ];
+    for (platform, profile_id) in valid {
+        assert!(try_link_profile(&env, platform, profile_id));
+    }
+
+    let invalid = [
+        (
+            Platform::Stellar,
+            "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2",
+        ),
+        (
+            Platform::Stellar,
+            "SBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H",
+        ),
+        (Platform::Email, "user.socket.fi"),
+        (Platform::Email, "user@@socket.fi"),
+        (Platform::Email, "@socket.fi"),
+        (Platform::Email, "user@socketfi"),
+        (Platform::X, "socketfi"),
+        (Platform::X, ""),
+        (Platform::Discord, "1234"),
+        (Platform::Telegram, "123456789012345678901"),
+    ];
+    for (platform, profile_id) in invalid {
+        assert!(!try_link_profile(&env, platform, profile_id));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Invalid profile id for platform")]
+fn test_init_with_invalid_profile_id() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, crate::SubAccount);
+    let client = SubAccountClient::new(&env, &contract_id);
+    let value = String::from_str(&env, "encrypted");
+
+    client.init_with_profile(
+        &Address::generate(&env),
+        &Platform::Discord,
+        &String::from_str(&env, "socketfi"),
+        &value,
+        &value,
+        &value,
+        &value,
+        &passkey(&env),
+        &1_000,
+    );
+}
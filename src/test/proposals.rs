@@ -0,0 +1,309 @@
+use super::{
+    create_account_with_address, create_token, env_with_long_ttl, fund_account, AddressSetup,
+};
+use crate::data::{ProposalAction, ProposalStatus, SignerCategory, PROPOSAL_EXPIRY};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, IntoVal, String, Symbol,
+};
+
+//Adds a second signer and requires both for transfers
+fn add_second_signer(env: &Env, setup: &AddressSetup) -> Address {
+    let signer = Address::generate(env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Transfer, &2);
+    signer
+}
+
+#[test]
+fn test_propose_approve_and_execute_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let signer = add_second_signer(&env, &setup);
+    let recipient = Address::generate(&env);
+
+    let action = ProposalAction::Transfer(recipient.clone(), token.address.clone(), 200);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    assert_eq!(proposal_id, 1);
+    assert_eq!(setup.client.get_proposal_count(), 1);
+
+    //the proposer's approval alone is below the threshold
+    assert!(setup.client.try_execute_proposal(&proposal_id).is_err());
+
+    setup.client.approve_proposal(&signer, &proposal_id);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (
+                    symbol_short!("proposal"),
+                    symbol_short!("approved"),
+                    proposal_id
+                )
+                    .into_val(&env),
+                signer.into_val(&env),
+            )
+        ]
+    );
+
+    setup.client.execute_proposal(&proposal_id);
+    assert_eq!(token.balance(&recipient), 200);
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+
+    let proposal = setup.client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+    assert_eq!(proposal.approvals.len(), 2);
+    assert_eq!(proposal.action, action);
+}
+
+#[test]
+fn test_execute_contract_call() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+    let spender = Address::generate(&env);
+
+    let args = vec![
+        &env,
+        setup.client.address.into_val(&env),
+        spender.into_val(&env),
+        100_i128.into_val(&env),
+        1_000_u32.into_val(&env),
+    ];
+    let action = ProposalAction::Invoke(token.address.clone(), Symbol::new(&env, "approve"), args);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    setup.client.execute_proposal(&proposal_id);
+
+    assert_eq!(token.allowance(&setup.client.address, &spender), 100);
+}
+
+#[test]
+fn test_contract_call_moving_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let args = vec![
+        &env,
+        setup.client.address.into_val(&env),
+        Address::generate(&env).into_val(&env),
+        200_i128.into_val(&env),
+    ];
+    let action = ProposalAction::Invoke(token.address.clone(), Symbol::new(&env, "transfer"), args);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    setup.client.execute_proposal(&proposal_id);
+
+    //the transferred tokens are no longer counted, so a later direct transfer can be credited
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+    token_admin.mint(&setup.client.address, &100);
+    assert_eq!(setup.client.credit_deposit(&token.address), 100);
+}
+
+#[test]
+#[should_panic(expected = "Contract call cannot move reserved or approved funds")]
+fn test_contract_call_cannot_move_reserved_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "holiday"),
+        &token.address,
+        &0,
+        &0,
+    );
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &300);
+
+    let args = vec![
+        &env,
+        setup.client.address.into_val(&env),
+        Address::generate(&env).into_val(&env),
+        201_i128.into_val(&env),
+    ];
+    let action = ProposalAction::Invoke(token.address.clone(), Symbol::new(&env, "transfer"), args);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    setup.client.execute_proposal(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Proposal approvals do not meet the threshold")]
+fn test_contract_call_requires_config_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+    let signer = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Config, &2);
+
+    //the transfer threshold is still 1, a single approval is not enough for a call
+    let args = vec![
+        &env,
+        setup.client.address.into_val(&env),
+        signer.into_val(&env),
+        100_i128.into_val(&env),
+        1_000_u32.into_val(&env),
+    ];
+    let action = ProposalAction::Invoke(token.address.clone(), Symbol::new(&env, "approve"), args);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    setup.client.execute_proposal(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Address is not a signer")]
+fn test_propose_by_non_signer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+
+    let action = ProposalAction::Transfer(Address::generate(&env), token.address, 200);
+    setup
+        .client
+        .propose_transaction(&Address::generate(&env), &action);
+}
+
+#[test]
+#[should_panic(expected = "Proposal already approved by signer")]
+fn test_approve_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+
+    let action = ProposalAction::Transfer(Address::generate(&env), token.address, 200);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    setup.client.approve_proposal(&setup.owner, &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Proposal approvals do not meet the threshold")]
+fn test_removed_signer_approval_is_not_counted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let signer = add_second_signer(&env, &setup);
+    let third = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &third, &1);
+
+    let action = ProposalAction::Transfer(Address::generate(&env), token.address, 200);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    setup.client.approve_proposal(&signer, &proposal_id);
+    setup.client.remove_signer(&setup.signers, &signer);
+
+    setup.client.execute_proposal(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Proposal has expired")]
+fn test_proposal_expiry() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let action = ProposalAction::Transfer(Address::generate(&env), token.address, 200);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    let proposal = setup.client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(
+        proposal.expiration_ledger,
+        env.ledger().sequence() + PROPOSAL_EXPIRY
+    );
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += PROPOSAL_EXPIRY + 1);
+    assert_eq!(
+        setup.client.get_proposal(&proposal_id).unwrap().status,
+        ProposalStatus::Expired
+    );
+    setup.client.execute_proposal(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Proposal is not pending")]
+fn test_revoke_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let action = ProposalAction::Transfer(Address::generate(&env), token.address, 200);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    setup.client.revoke_proposal(&setup.owner, &proposal_id);
+    assert_eq!(
+        setup.client.get_proposal(&proposal_id).unwrap().status,
+        ProposalStatus::Revoked
+    );
+
+    setup.client.execute_proposal(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Only the proposer can revoke a proposal")]
+fn test_revoke_by_other_signer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+    let signer = add_second_signer(&env, &setup);
+
+    let action = ProposalAction::Transfer(Address::generate(&env), token.address, 200);
+    let proposal_id = setup.client.propose_transaction(&setup.owner, &action);
+    setup.client.revoke_proposal(&signer, &proposal_id);
+}
+
+#[test]
+fn test_get_proposals_by_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    for _ in 0..4 {
+        let action = ProposalAction::Transfer(Address::generate(&env), token.address.clone(), 50);
+        setup.client.propose_transaction(&setup.owner, &action);
+    }
+    setup.client.execute_proposal(&2);
+    setup.client.revoke_proposal(&setup.owner, &3);
+
+    let pending = setup
+        .client
+        .get_proposals(&ProposalStatus::Pending, &1, &10);
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending.get(0).unwrap().id, 1);
+    assert_eq!(pending.get(1).unwrap().id, 4);
+
+    //only ids 3 and 4 are checked
+    let page = setup.client.get_proposals(&ProposalStatus::Pending, &3, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, 4);
+
+    let executed = setup
+        .client
+        .get_proposals(&ProposalStatus::Executed, &1, &10);
+    assert_eq!(executed.len(), 1);
+    assert_eq!(executed.get(0).unwrap().id, 2);
+}
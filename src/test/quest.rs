@@ -0,0 +1,284 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, env_with_long_ttl,
+    fund_account, set_owner_pkey, try_set_allowance, PRIMARY,
+};
+use crate::{
+    data::{Memo, QuestAction, QuestRule, DAY_IN_LEDGERS},
+    types::UserPoints,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, IntoVal,
+};
+
+#[test]
+fn test_points_on_init() {
+    let env = Env::default();
+    let address_setup = create_account_with_address(&env);
+    let profile_setup = create_account_with_profile(&env);
+
+    let expected = UserPoints {
+        has_received: false,
+        has_sent: false,
+        has_set_allowance: false,
+        has_set_signer: false,
+        points: 2000,
+    };
+    assert_eq!(address_setup.client.get_user_points(), expected);
+    assert_eq!(profile_setup.client.get_user_points(), expected);
+}
+
+#[test]
+fn test_points_on_receive() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (_, token_admin) = create_token(&env);
+
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let points = setup.client.get_user_points();
+    assert!(points.has_received);
+    assert_eq!(points.points, 2250);
+}
+
+#[test]
+fn test_points_on_set_allowance_and_signer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let owner = Address::generate(&env);
+
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 250));
+    set_owner_pkey(&env, &setup.client, &owner);
+
+    let points = setup.client.get_user_points();
+    assert!(points.has_set_allowance);
+    assert!(points.has_set_signer);
+    assert_eq!(points.points, 3000);
+    assert_eq!(setup.client.get_signer_weight(&owner), 1);
+}
+
+#[test]
+fn test_set_allowance_points_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 250));
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 300));
+
+    assert_eq!(setup.client.get_user_points().points, 2500);
+}
+
+#[test]
+fn test_receive_points_cooldown() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (_, token_admin) = create_token(&env);
+
+    fund_account(&env, &setup.client, &token_admin, 500);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    assert_eq!(setup.client.get_user_points().points, 2250);
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += DAY_IN_LEDGERS);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    assert_eq!(setup.client.get_user_points().points, 2500);
+}
+
+#[test]
+fn test_receive_points_min_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (_, token_admin) = create_token(&env);
+    let rule = QuestRule {
+        points: 100,
+        repeatable: true,
+        min_amount: 1_000,
+        cooldown: 0,
+    };
+    setup.client.set_quest_rule(&QuestAction::Receive, &rule);
+    assert_eq!(setup.client.get_quest_rule(&QuestAction::Receive), rule);
+
+    fund_account(&env, &setup.client, &token_admin, 999);
+    let points = setup.client.get_user_points();
+    assert!(points.has_received);
+    assert_eq!(points.points, 2000);
+
+    fund_account(&env, &setup.client, &token_admin, 1_000);
+    fund_account(&env, &setup.client, &token_admin, 1_000);
+    assert_eq!(setup.client.get_user_points().points, 2200);
+}
+
+#[test]
+fn test_receive_with_max_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (_, token_admin) = create_token(&env);
+    let rule = QuestRule {
+        points: u32::MAX,
+        repeatable: true,
+        min_amount: 0,
+        cooldown: u32::MAX,
+    };
+    setup.client.set_quest_rule(&QuestAction::Receive, &rule);
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+
+    //the points and the cooldown saturate instead of failing the receive
+    fund_account(&env, &setup.client, &token_admin, 500);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    assert_eq!(setup.client.get_user_points().points, u32::MAX);
+    assert_eq!(setup.client.get_balance(&token_admin.address), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "The init reward cannot be configured")]
+fn test_set_init_quest_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let rule = QuestRule {
+        points: 100,
+        repeatable: false,
+        min_amount: 0,
+        cooldown: 0,
+    };
+
+    setup.client.set_quest_rule(&QuestAction::Init, &rule);
+}
+
+#[test]
+fn test_points_on_send() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    setup.client.send_auth_addr(
+        &setup.signers,
+        &Address::generate(&env),
+        &token.address,
+        &100,
+        &Memo::None,
+    );
+
+    let points = setup.client.get_user_points();
+    assert!(points.has_sent);
+    assert_eq!(points.points, 2500);
+}
+
+#[test]
+fn test_disabled_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let rule = QuestRule {
+        points: 0,
+        repeatable: false,
+        min_amount: 0,
+        cooldown: 0,
+    };
+    setup.client.set_quest_rule(&QuestAction::SetSigner, &rule);
+
+    set_owner_pkey(&env, &setup.client, &Address::generate(&env));
+
+    let points = setup.client.get_user_points();
+    assert!(points.has_set_signer);
+    assert_eq!(points.points, 2000);
+}
+
+#[test]
+fn test_points_awarded_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    assert!(try_set_allowance(&env, &setup.client, &PRIMARY, 250));
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (symbol_short!("quest"), QuestAction::SetAllowance).into_val(&env),
+                (500_u32, 2500_u32).into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_set_quest_rule_without_controller_auth() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+    let rule = QuestRule {
+        points: 1_000_000,
+        repeatable: true,
+        min_amount: 0,
+        cooldown: 0,
+    };
+
+    setup.client.set_quest_rule(&QuestAction::Receive, &rule);
+}
+
+#[test]
+#[should_panic(expected = "Owner has already been set")]
+fn test_set_owner_pkey_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    set_owner_pkey(&env, &setup.client, &Address::generate(&env));
+    set_owner_pkey(&env, &setup.client, &Address::generate(&env));
+}
+
+#[test]
+fn test_update_user_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let user_data = UserPoints {
+        has_received: true,
+        has_sent: true,
+        has_set_allowance: false,
+        has_set_signer: false,
+        points: 9000,
+    };
+
+    setup.client.update_user_points(&setup.executor, &user_data);
+    assert_eq!(setup.client.get_user_points(), user_data);
+}
+
+#[test]
+#[should_panic(expected = "caller is not an executor")]
+fn test_update_user_points_not_executor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let mut user_data = setup.client.get_user_points();
+    user_data.points = 9000;
+
+    setup
+        .client
+        .update_user_points(&Address::generate(&env), &user_data);
+}
+
+#[test]
+#[should_panic]
+fn test_update_user_points_without_executor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+    let mut user_data = setup.client.get_user_points();
+    user_data.points = 9000;
+
+    setup.client.update_user_points(&setup.executor, &user_data);
+}
@@ -0,0 +1,148 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, sign_pkey, PRIMARY,
+};
+use crate::data::{AuthPath, Memo, PaymentRequestStatus, TransactionDirection};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env,
+};
+
+#[test]
+fn test_pay_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let payer = Address::generate(&env);
+    token_admin.mint(&payer, &500);
+    let memo = Memo::Hash(BytesN::from_array(&env, &[7; 32]));
+
+    let request_id =
+        setup
+            .client
+            .create_request_addr(&setup.signers, &token.address, &200, &1_000, &memo);
+    assert_eq!(request_id, 1);
+    let request = setup.client.get_payment_request(&request_id).unwrap();
+    assert_eq!(request.status, PaymentRequestStatus::Open);
+    assert_eq!(request.payer, None);
+
+    setup.client.pay_request(&payer, &request_id);
+
+    assert_eq!(token.balance(&payer), 300);
+    assert_eq!(setup.client.get_balance(&token.address), 200);
+    let request = setup.client.get_payment_request(&request_id).unwrap();
+    assert_eq!(request.status, PaymentRequestStatus::Paid);
+    assert_eq!(request.payer, Some(payer.clone()));
+
+    //the payment is recorded like any receive, with the request memo
+    let record = setup.client.get_transactions(&0, &1).get(0).unwrap();
+    assert_eq!(record.direction, TransactionDirection::Received);
+    assert_eq!(record.counterparty, payer);
+    assert_eq!(record.auth, AuthPath::Depositor);
+    assert_eq!(record.memo, memo);
+}
+
+#[test]
+#[should_panic(expected = "Payment request is not open")]
+fn test_pay_request_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let payer = Address::generate(&env);
+    token_admin.mint(&payer, &500);
+    let request_id =
+        setup
+            .client
+            .create_request_addr(&setup.signers, &token.address, &200, &1_000, &Memo::None);
+
+    setup.client.pay_request(&payer, &request_id);
+    setup.client.pay_request(&payer, &request_id);
+}
+
+#[test]
+#[should_panic(expected = "Payment request has expired")]
+fn test_pay_expired_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let payer = Address::generate(&env);
+    token_admin.mint(&payer, &500);
+    let request_id =
+        setup
+            .client
+            .create_request_addr(&setup.signers, &token.address, &200, &1_000, &Memo::None);
+    env.ledger().with_mut(|li| li.sequence_number = 1_001);
+    assert_eq!(
+        setup
+            .client
+            .get_payment_request(&request_id)
+            .unwrap()
+            .status,
+        PaymentRequestStatus::Expired
+    );
+
+    setup.client.pay_request(&payer, &request_id);
+}
+
+#[test]
+#[should_panic(expected = "Payment request is not open")]
+fn test_pay_cancelled_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let payer = Address::generate(&env);
+    token_admin.mint(&payer, &500);
+    let request_id =
+        setup
+            .client
+            .create_request_addr(&setup.signers, &token.address, &200, &1_000, &Memo::None);
+    setup
+        .client
+        .cancel_request_addr(&setup.signers, &request_id);
+
+    setup.client.pay_request(&payer, &request_id);
+}
+
+#[test]
+fn test_create_and_cancel_request_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_request_pkey",
+        (token.address.clone(), 200_i128, 1_000_u32, Memo::None),
+    );
+    let request_id = setup.client.create_request_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &token.address,
+        &200,
+        &1_000,
+        &Memo::None,
+    );
+    assert_eq!(request_id, Some(1));
+    assert_eq!(setup.client.get_payment_request_count(), 1);
+
+    let auth = sign_pkey(&env, &setup.client, &PRIMARY, "cancel_request_pkey", 1_u32);
+    assert!(setup.client.cancel_request_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &1
+    ));
+    assert_eq!(
+        setup.client.get_payment_request(&1).unwrap().status,
+        PaymentRequestStatus::Cancelled
+    );
+}
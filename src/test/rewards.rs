@@ -0,0 +1,210 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, sign_pkey, PRIMARY,
+    WRONG,
+};
+use crate::data::RewardOffer;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+//Publishes an airdrop offer funded by the controller allowance
+fn publish_offer(
+    env: &Env,
+    client: &crate::SubAccountClient,
+    controller: &Address,
+    token: &TokenClient,
+    token_admin: &StellarAssetClient,
+    cost: u32,
+    amount: i128,
+) -> RewardOffer {
+    token_admin.mint(controller, &10_000);
+    token.approve(controller, &client.address, &10_000, &1_000);
+    let offer = RewardOffer {
+        cost,
+        token_id: token.address.clone(),
+        amount,
+        expiration_ledger: env.ledger().sequence() + 100,
+    };
+    client.set_reward_offer(&1, &offer);
+    offer
+}
+
+#[test]
+fn test_redeem_points_addr() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let offer = publish_offer(
+        &env,
+        &setup.client,
+        &setup.controller,
+        &token,
+        &token_admin,
+        1_500,
+        300,
+    );
+    assert_eq!(setup.client.get_reward_offer(&1), Some(offer));
+
+    setup.client.redeem_points_addr(&setup.signers, &1);
+
+    assert_eq!(setup.client.get_user_points().points, 500);
+    assert_eq!(token.balance(&setup.client.address), 300);
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+    assert_eq!(setup.client.get_tokens().len(), 1);
+
+    assert_eq!(setup.client.get_redemption_count(), 1);
+    let redemption = setup.client.get_redemptions(&1, &10).get(0).unwrap();
+    assert_eq!(redemption.offer_id, 1);
+    assert_eq!(redemption.cost, 1_500);
+    assert_eq!(redemption.token_id, token.address);
+    assert_eq!(redemption.amount, 300);
+}
+
+#[test]
+fn test_redeem_points_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    publish_offer(
+        &env,
+        &setup.client,
+        &setup.controller,
+        &token,
+        &token_admin,
+        1_000,
+        100,
+    );
+
+    for _ in 0..2 {
+        let auth = sign_pkey(&env, &setup.client, &PRIMARY, "redeem_points_pkey", 1_u32);
+        setup
+            .client
+            .redeem_points_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof, &1);
+    }
+
+    assert_eq!(setup.client.get_user_points().points, 0);
+    assert_eq!(setup.client.get_balance(&token.address), 200);
+    assert_eq!(setup.client.get_redemption_count(), 2);
+    assert_eq!(setup.client.get_redemptions(&2, &10).len(), 1);
+    assert_eq!(setup.client.get_redemptions(&3, &10).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Not enough points to redeem this offer")]
+fn test_redeem_not_enough_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    publish_offer(
+        &env,
+        &setup.client,
+        &setup.controller,
+        &token,
+        &token_admin,
+        2_001,
+        100,
+    );
+
+    setup.client.redeem_points_addr(&setup.signers, &1);
+}
+
+#[test]
+#[should_panic(expected = "Reward offer has expired")]
+fn test_redeem_expired_offer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    publish_offer(
+        &env,
+        &setup.client,
+        &setup.controller,
+        &token,
+        &token_admin,
+        1_000,
+        100,
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 101);
+    setup.client.redeem_points_addr(&setup.signers, &1);
+}
+
+#[test]
+#[should_panic(expected = "Reward offer not found")]
+fn test_redeem_removed_offer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    publish_offer(
+        &env,
+        &setup.client,
+        &setup.controller,
+        &token,
+        &token_admin,
+        1_000,
+        100,
+    );
+
+    setup.client.remove_reward_offer(&1);
+    setup.client.redeem_points_addr(&setup.signers, &1);
+}
+
+#[test]
+fn test_redeem_without_controller_allowance_keeps_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+    let offer = RewardOffer {
+        cost: 1_000,
+        token_id: token.address.clone(),
+        amount: 100,
+        expiration_ledger: env.ledger().sequence() + 100,
+    };
+    setup.client.set_reward_offer(&1, &offer);
+
+    assert!(setup
+        .client
+        .try_redeem_points_addr(&setup.signers, &1)
+        .is_err());
+    assert_eq!(setup.client.get_user_points().points, 2_000);
+    assert_eq!(setup.client.get_redemption_count(), 0);
+}
+
+#[test]
+fn test_redeem_wrong_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let auth = sign_pkey(&env, &setup.client, &WRONG, "redeem_points_pkey", 1_u32);
+    assert!(!setup.client.redeem_points_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &1
+    ));
+    assert_eq!(setup.client.get_redemption_count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_set_reward_offer_without_controller_auth() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+    let offer = RewardOffer {
+        cost: 1,
+        token_id: Address::generate(&env),
+        amount: 1_000_000,
+        expiration_ledger: 100,
+    };
+
+    setup.client.set_reward_offer(&1, &offer);
+}
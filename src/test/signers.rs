@@ -0,0 +1,221 @@
+use super::{create_account_with_address, create_token, fund_account};
+use crate::data::{Memo, Signer, SignerCategory};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, MockAuth, MockAuthInvoke},
+    vec, Address, Env, IntoVal,
+};
+
+#[test]
+fn test_add_signer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let signer = Address::generate(&env);
+
+    setup.client.add_signer(&setup.signers, &signer, &2);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                setup.client.address.clone(),
+                (
+                    symbol_short!("signer"),
+                    symbol_short!("weight"),
+                    signer.clone()
+                )
+                    .into_val(&env),
+                2_u32.into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(
+        setup.client.get_signers(),
+        vec![
+            &env,
+            Signer {
+                address: setup.owner.clone(),
+                weight: 1,
+            },
+            Signer {
+                address: signer.clone(),
+                weight: 2,
+            },
+        ]
+    );
+
+    setup.client.set_signer_weight(&setup.signers, &signer, &3);
+    assert_eq!(setup.client.get_signer_weight(&signer), 3);
+
+    setup.client.remove_signer(&setup.signers, &signer);
+    assert_eq!(setup.client.get_signer_weight(&signer), 0);
+    assert_eq!(setup.client.get_signers().len(), 1);
+}
+
+#[test]
+fn test_transfer_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let signer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Transfer, &2);
+    assert_eq!(setup.client.get_threshold(&SignerCategory::Transfer), 2);
+    assert_eq!(setup.client.get_threshold(&SignerCategory::Config), 1);
+
+    //one signer alone can no longer send
+    assert!(setup
+        .client
+        .try_send_auth_addr(
+            &setup.signers,
+            &recipient,
+            &token.address,
+            &200,
+            &Memo::None
+        )
+        .is_err());
+
+    let both = vec![&env, setup.owner.clone(), signer];
+    setup
+        .client
+        .send_auth_addr(&both, &recipient, &token.address, &200, &Memo::None);
+    assert_eq!(token.balance(&recipient), 200);
+}
+
+#[test]
+fn test_every_listed_signer_must_authorize() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let signer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Transfer, &2);
+
+    let both = vec![&env, setup.owner.clone(), signer];
+    let result = setup
+        .client
+        .mock_auths(&[MockAuth {
+            address: &setup.owner,
+            invoke: &MockAuthInvoke {
+                contract: &setup.client.address,
+                fn_name: "send_auth_addr",
+                args: (
+                    both.clone(),
+                    &recipient,
+                    &token.address,
+                    200_i128,
+                    Memo::None,
+                )
+                    .into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_send_auth_addr(&both, &recipient, &token.address, &200, &Memo::None);
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Signer threshold not met")]
+fn test_config_threshold_applies_to_signer_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let signer = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Config, &2);
+
+    setup
+        .client
+        .add_signer(&setup.signers, &Address::generate(&env), &1);
+}
+
+#[test]
+#[should_panic(expected = "Signer threshold cannot exceed the total weight")]
+fn test_threshold_above_total_weight() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Upgrade, &2);
+}
+
+#[test]
+#[should_panic(expected = "Signer threshold cannot exceed the total weight")]
+fn test_remove_signer_below_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let signer = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Upgrade, &2);
+
+    setup.client.remove_signer(&setup.signers, &signer);
+}
+
+#[test]
+#[should_panic(expected = "Address is not a signer")]
+fn test_unknown_signer_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+
+    setup
+        .client
+        .set_allowance_with_addr(&vec![&env, Address::generate(&env)], &250);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate signer")]
+fn test_duplicate_signer_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let signer = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Config, &2);
+
+    let twice = vec![&env, setup.owner.clone(), setup.owner.clone()];
+    setup.client.set_allowance_with_addr(&twice, &250);
+}
+
+#[test]
+fn test_accept_owner_resets_signer_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let signer = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    setup.client.add_signer(&setup.signers, &signer, &1);
+    setup
+        .client
+        .set_threshold(&setup.signers, &SignerCategory::Transfer, &2);
+
+    setup.client.propose_owner(&setup.signers, &new_owner);
+    setup.client.accept_owner();
+
+    assert_eq!(setup.client.get_signer_weight(&setup.owner), 0);
+    assert_eq!(setup.client.get_signer_weight(&signer), 0);
+    assert_eq!(setup.client.get_signer_weight(&new_owner), 1);
+    assert_eq!(setup.client.get_threshold(&SignerCategory::Transfer), 1);
+}
@@ -0,0 +1,127 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, fund_account,
+    sign_pkey, PRIMARY, WRONG,
+};
+use soroban_sdk::Env;
+
+#[test]
+fn test_set_smart_transact_addr() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    setup
+        .client
+        .set_smart_transact_addr(&setup.signers, &token.address, &true);
+    assert!(setup.client.get_tokens().get(0).unwrap().smart_transact);
+
+    setup
+        .client
+        .set_smart_transact_addr(&setup.signers, &token.address, &false);
+    assert!(!setup.client.get_tokens().get(0).unwrap().smart_transact);
+}
+
+#[test]
+#[should_panic]
+fn test_set_smart_transact_addr_without_auth() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+
+    setup
+        .client
+        .set_smart_transact_addr(&setup.signers, &token.address, &true);
+}
+
+#[test]
+fn test_set_smart_transact_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_smart_transact_pkey",
+        (token.address.clone(), true),
+    );
+    setup.client.set_smart_transact_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &token.address,
+        &true,
+    );
+    assert!(setup.client.get_tokens().get(0).unwrap().smart_transact);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_smart_transact_pkey",
+        (token.address.clone(), false),
+    );
+    setup.client.set_smart_transact_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &token.address,
+        &false,
+    );
+    assert!(!setup.client.get_tokens().get(0).unwrap().smart_transact);
+}
+
+#[test]
+fn test_set_smart_transact_wrong_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &WRONG,
+        "set_smart_transact_pkey",
+        (token.address.clone(), true),
+    );
+    assert!(!setup.client.set_smart_transact_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &token.address,
+        &true,
+    ));
+}
+
+#[test]
+#[should_panic]
+fn test_set_smart_transact_pkey_without_executor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "set_smart_transact_pkey",
+        (token.address.clone(), true),
+    );
+    setup.client.set_smart_transact_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &token.address,
+        &true,
+    );
+}
@@ -0,0 +1,246 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, fund_account,
+    sign_pkey, AddressSetup, PRIMARY,
+};
+use crate::data::{Memo, StreamTerms};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn stream_terms(env: &Env, token_id: &Address, rate_per_ledger: i128) -> StreamTerms {
+    let start_ledger = env.ledger().sequence();
+    StreamTerms {
+        recipient: Address::generate(env),
+        token_id: token_id.clone(),
+        rate_per_ledger,
+        start_ledger,
+        end_ledger: start_ledger + 100,
+        cliff_ledger: start_ledger + 10,
+    }
+}
+
+fn advance_ledger(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+fn send(env: &Env, setup: &AddressSetup, token_id: &Address, amount: i128) {
+    setup.client.send_auth_addr(
+        &setup.signers,
+        &Address::generate(env),
+        token_id,
+        &amount,
+        &Memo::None,
+    );
+}
+
+#[test]
+fn test_stream_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let terms = stream_terms(&env, &token.address, 2);
+    let stream_id = setup.client.create_stream_addr(&setup.signers, &terms);
+    assert_eq!(stream_id, 1);
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 200);
+
+    //nothing is withdrawable before the cliff
+    advance_ledger(&env, 5);
+    assert_eq!(setup.client.get_stream_withdrawable(&stream_id), 0);
+    assert!(setup.client.try_withdraw_stream(&stream_id).is_err());
+
+    advance_ledger(&env, 45);
+    assert_eq!(setup.client.get_stream_withdrawable(&stream_id), 100);
+    assert_eq!(setup.client.withdraw_stream(&stream_id), 100);
+    assert_eq!(token.balance(&terms.recipient), 100);
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 100);
+    assert_eq!(setup.client.get_stream(&stream_id).unwrap().withdrawn, 100);
+
+    //accrual stops at the end ledger
+    advance_ledger(&env, 500);
+    assert_eq!(setup.client.withdraw_stream(&stream_id), 100);
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Amount exceeds the unreserved balance")]
+fn test_send_cannot_spend_reserved_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    setup
+        .client
+        .create_stream_addr(&setup.signers, &stream_terms(&env, &token.address, 2));
+
+    send(&env, &setup, &token.address, 300);
+    send(&env, &setup, &token.address, 1);
+}
+
+#[test]
+fn test_cancel_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let terms = stream_terms(&env, &token.address, 2);
+    let stream_id = setup.client.create_stream_addr(&setup.signers, &terms);
+
+    advance_ledger(&env, 20);
+    setup.client.cancel_stream_addr(&setup.signers, &stream_id);
+
+    //the accrued amount is paid and the rest is no longer reserved
+    assert_eq!(token.balance(&terms.recipient), 40);
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 0);
+    let stream = setup.client.get_stream(&stream_id).unwrap();
+    assert!(stream.cancelled);
+    assert_eq!(setup.client.get_stream_withdrawable(&stream_id), 0);
+    send(&env, &setup, &token.address, 460);
+}
+
+#[test]
+#[should_panic(expected = "Stream has already been cancelled")]
+fn test_cancel_stream_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let stream_id = setup
+        .client
+        .create_stream_addr(&setup.signers, &stream_terms(&env, &token.address, 2));
+
+    setup.client.cancel_stream_addr(&setup.signers, &stream_id);
+    setup.client.cancel_stream_addr(&setup.signers, &stream_id);
+}
+
+#[test]
+#[should_panic(expected = "Not enough unreserved balance for the stream")]
+fn test_stream_above_unreserved_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    setup
+        .client
+        .create_stream_addr(&setup.signers, &stream_terms(&env, &token.address, 3));
+
+    setup
+        .client
+        .create_stream_addr(&setup.signers, &stream_terms(&env, &token.address, 3));
+}
+
+#[test]
+#[should_panic(expected = "Not enough unreserved balance for the stream")]
+fn test_stream_cannot_reserve_approved_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    setup.client.approve_spender_addr(
+        &setup.signers,
+        &token.address,
+        &Address::generate(&env),
+        &500,
+        &1_000,
+    );
+
+    //the spender could pull the whole balance and leave the stream unfunded
+    setup
+        .client
+        .create_stream_addr(&setup.signers, &stream_terms(&env, &token.address, 5));
+}
+
+#[test]
+#[should_panic(expected = "Invalid stream schedule")]
+fn test_cliff_after_end() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let mut terms = stream_terms(&env, &token.address, 2);
+    terms.cliff_ledger = terms.end_ledger + 1;
+    setup.client.create_stream_addr(&setup.signers, &terms);
+}
+
+#[test]
+fn test_create_stream_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let terms = stream_terms(&env, &token.address, 2);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_stream_pkey",
+        terms.clone(),
+    );
+    let stream_id = setup.client.create_stream_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &terms,
+    );
+    assert_eq!(stream_id, Some(1));
+    assert_eq!(setup.client.get_stream_count(), 1);
+    assert_eq!(setup.client.get_stream(&1).unwrap().terms, terms);
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 200);
+}
+
+#[test]
+#[should_panic(expected = "You cannot stream an amount greater than your allowance")]
+fn test_create_stream_pkey_above_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 5_000);
+
+    //100 ledgers at 11 per ledger is above the allowance of 1000
+    let terms = stream_terms(&env, &token.address, 11);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_stream_pkey",
+        terms.clone(),
+    );
+    setup.client.create_stream_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &terms,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Stream cannot start in the past")]
+fn test_back_dated_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    advance_ledger(&env, 50);
+
+    let mut terms = stream_terms(&env, &token.address, 2);
+    terms.start_ledger -= 50;
+    terms.cliff_ledger = terms.start_ledger;
+    setup.client.create_stream_addr(&setup.signers, &terms);
+}
@@ -0,0 +1,88 @@
+use super::{create_account_with_address, create_account_with_profile, create_token, fund_account};
+use crate::data::{DataKey, TokenMetadata};
+use soroban_sdk::{Bytes, Env, String};
+
+#[test]
+fn test_token_metadata_cached_on_receive() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let expected = TokenMetadata {
+        decimals: 7,
+        symbol: token.symbol(),
+        name: token.name(),
+    };
+    let cached = env.as_contract(&setup.client.address, || {
+        env.storage()
+            .persistent()
+            .get::<DataKey, TokenMetadata>(&DataKey::TokenMetadata(token.address.clone()))
+    });
+    assert_eq!(cached, Some(expected.clone()));
+    assert_eq!(setup.client.get_token_metadata(&token.address), expected);
+
+    let listed = setup.client.get_tokens().get(0).unwrap();
+    assert_eq!(listed.decimals, 7);
+    assert_eq!(listed.symbol, expected.symbol);
+    assert_eq!(listed.name, expected.name);
+    assert!(!listed.is_native);
+}
+
+#[test]
+fn test_metadata_of_token_registered_before_caching() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let key = DataKey::TokenMetadata(token.address.clone());
+    env.as_contract(&setup.client.address, || {
+        env.storage().persistent().remove(&key);
+    });
+
+    assert_eq!(
+        setup.client.get_token_metadata(&token.address).symbol,
+        token.symbol()
+    );
+    let cached = env.as_contract(&setup.client.address, || {
+        env.storage().persistent().has(&key)
+    });
+    assert!(!cached);
+}
+
+#[test]
+fn test_metadata_of_unregistered_token_not_cached() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+    let (token, _) = create_token(&env);
+
+    assert_eq!(setup.client.get_token_metadata(&token.address).decimals, 7);
+    let key = DataKey::TokenMetadata(token.address.clone());
+    let cached = env.as_contract(&setup.client.address, || {
+        env.storage().persistent().has(&key)
+    });
+    assert!(!cached);
+}
+
+#[test]
+fn test_native_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let native = env
+        .deployer()
+        .with_stellar_asset(Bytes::from_array(&env, &[0, 0, 0, 0]))
+        .deploy();
+    assert_eq!(setup.client.get_native_token(), native);
+
+    setup
+        .client
+        .set_dashboard_balance(&setup.executor, &native, &700);
+
+    let listed = setup.client.get_tokens().get(0).unwrap();
+    assert!(listed.is_native);
+    assert_eq!(listed.decimals, 7);
+    assert_eq!(listed.symbol, String::from_str(&env, "native"));
+}
@@ -0,0 +1,446 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, fund_account,
+    sign_pkey, PRIMARY, WRONG,
+};
+use crate::data::{AuthPath, Memo, TransactionDirection};
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal, Vec,
+};
+
+#[test]
+fn test_receive() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+
+    let depositor = fund_account(&env, &setup.client, &token_admin, 500);
+
+    assert_eq!(token.balance(&depositor), 0);
+    assert_eq!(token.balance(&setup.client.address), 500);
+    assert_eq!(setup.client.get_balance(&token.address), 500);
+    assert_eq!(setup.client.get_tx_count(), 1);
+
+    let tokens = setup.client.get_tokens();
+    assert_eq!(tokens.len(), 1);
+    let received = tokens.get(0).unwrap();
+    assert_eq!(received.token_id, token.address);
+    assert_eq!(received.balance, 500);
+    assert!(!received.smart_transact);
+}
+
+#[test]
+fn test_receive_same_token_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+
+    fund_account(&env, &setup.client, &token_admin, 500);
+    fund_account(&env, &setup.client, &token_admin, 300);
+
+    assert_eq!(setup.client.get_balance(&token.address), 800);
+    assert_eq!(setup.client.get_tokens().len(), 1);
+    assert_eq!(setup.client.get_tx_count(), 2);
+}
+
+#[test]
+fn test_receive_with_mocked_depositor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let depositor = Address::generate(&env);
+    token_admin.mock_all_auths().mint(&depositor, &500);
+
+    setup
+        .client
+        .mock_auths(&[MockAuth {
+            address: &depositor,
+            invoke: &MockAuthInvoke {
+                contract: &setup.client.address,
+                fn_name: "receive",
+                args: (&depositor, &token.address, 500_i128, Memo::None).into_val(&env),
+                sub_invokes: &[MockAuthInvoke {
+                    contract: &token.address,
+                    fn_name: "transfer",
+                    args: (&depositor, &setup.client.address, 500_i128).into_val(&env),
+                    sub_invokes: &[],
+                }],
+            },
+        }])
+        .receive(&depositor, &token.address, &500, &Memo::None);
+
+    assert_eq!(setup.client.get_balance(&token.address), 500);
+}
+
+#[test]
+#[should_panic]
+fn test_receive_without_depositor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let depositor = Address::generate(&env);
+    token_admin.mock_all_auths().mint(&depositor, &500);
+
+    setup
+        .client
+        .receive(&depositor, &token.address, &500, &Memo::None);
+}
+
+#[test]
+fn test_send_auth_addr() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let recipient = Address::generate(&env);
+
+    setup.client.send_auth_addr(
+        &setup.signers,
+        &recipient,
+        &token.address,
+        &200,
+        &Memo::None,
+    );
+
+    assert_eq!(token.balance(&recipient), 200);
+    assert_eq!(token.balance(&setup.client.address), 300);
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+    assert_eq!(setup.client.get_tx_count(), 2);
+}
+
+#[test]
+fn test_send_auth_addr_with_mocked_owner_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let recipient = Address::generate(&env);
+
+    setup
+        .client
+        .mock_auths(&[MockAuth {
+            address: &setup.owner,
+            invoke: &MockAuthInvoke {
+                contract: &setup.client.address,
+                fn_name: "send_auth_addr",
+                args: (
+                    setup.signers.clone(),
+                    &recipient,
+                    &token.address,
+                    200_i128,
+                    Memo::None,
+                )
+                    .into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .send_auth_addr(
+            &setup.signers,
+            &recipient,
+            &token.address,
+            &200,
+            &Memo::None,
+        );
+
+    assert_eq!(token.balance(&recipient), 200);
+}
+
+#[test]
+#[should_panic]
+fn test_send_auth_addr_signed_by_other() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let recipient = Address::generate(&env);
+
+    setup
+        .client
+        .mock_auths(&[MockAuth {
+            address: &recipient,
+            invoke: &MockAuthInvoke {
+                contract: &setup.client.address,
+                fn_name: "send_auth_addr",
+                args: (
+                    setup.signers.clone(),
+                    &recipient,
+                    &token.address,
+                    200_i128,
+                    Memo::None,
+                )
+                    .into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .send_auth_addr(
+            &setup.signers,
+            &recipient,
+            &token.address,
+            &200,
+            &Memo::None,
+        );
+}
+
+#[test]
+#[should_panic(expected = "Owner not set")]
+fn test_send_auth_addr_without_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    setup.client.send_auth_addr(
+        &Vec::new(&env),
+        &Address::generate(&env),
+        &token.address,
+        &200,
+        &Memo::None,
+    );
+}
+
+#[test]
+fn test_send_with_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let recipient = Address::generate(&env);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "send_with_pkey",
+        (
+            recipient.clone(),
+            token.address.clone(),
+            200_i128,
+            Memo::None,
+        ),
+    );
+    setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &200,
+        &Memo::None,
+    );
+
+    assert_eq!(token.balance(&recipient), 200);
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+}
+
+#[test]
+fn test_send_with_wrong_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let recipient = Address::generate(&env);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &WRONG,
+        "send_with_pkey",
+        (
+            recipient.clone(),
+            token.address.clone(),
+            200_i128,
+            Memo::None,
+        ),
+    );
+    assert!(!setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &200,
+        &Memo::None,
+    ));
+    assert_eq!(token.balance(&recipient), 0);
+    assert_eq!(setup.client.get_balance(&token.address), 500);
+}
+
+#[test]
+#[should_panic]
+fn test_send_with_pkey_without_executor_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    env.set_auths(&[]);
+    let recipient = Address::generate(&env);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "send_with_pkey",
+        (
+            recipient.clone(),
+            token.address.clone(),
+            200_i128,
+            Memo::None,
+        ),
+    );
+    setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &200,
+        &Memo::None,
+    );
+}
+
+#[test]
+fn test_set_dashboard_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+
+    setup
+        .client
+        .set_dashboard_balance(&setup.executor, &token.address, &700);
+
+    assert_eq!(setup.client.get_balance(&token.address), 700);
+    assert_eq!(setup.client.get_tokens().len(), 1);
+}
+
+#[test]
+fn test_credit_deposit_after_dashboard_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    setup
+        .client
+        .set_dashboard_balance(&setup.executor, &token.address, &700);
+
+    //the dashboard balance does not hide deposits from credit_deposit
+    token_admin.mint(&setup.client.address, &100);
+    assert_eq!(setup.client.credit_deposit(&token.address), 100);
+    assert_eq!(setup.client.get_balance(&token.address), 800);
+}
+
+#[test]
+#[should_panic(expected = "caller is not an executor")]
+fn test_set_dashboard_balance_not_executor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+
+    setup
+        .client
+        .set_dashboard_balance(&Address::generate(&env), &token.address, &700);
+}
+
+#[test]
+#[should_panic]
+fn test_set_dashboard_balance_without_executor_auth() {
+    let env = Env::default();
+    let setup = create_account_with_profile(&env);
+    let (token, _) = create_token(&env);
+
+    setup
+        .client
+        .set_dashboard_balance(&setup.executor, &token.address, &700);
+}
+
+#[test]
+fn test_pull_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let depositor = Address::generate(&env);
+    token_admin.mint(&depositor, &500);
+    token.approve(&depositor, &setup.client.address, &500, &1_000);
+
+    setup
+        .client
+        .pull_deposit(&depositor, &token.address, &300, &Memo::None);
+
+    assert_eq!(token.balance(&depositor), 200);
+    assert_eq!(token.balance(&setup.client.address), 300);
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+    assert_eq!(token.allowance(&depositor, &setup.client.address), 200);
+    let record = setup.client.get_transactions(&0, &1).get(0).unwrap();
+    assert_eq!(record.direction, TransactionDirection::Received);
+    assert_eq!(record.counterparty, depositor);
+    assert_eq!(record.auth, AuthPath::Controller);
+}
+
+#[test]
+fn test_pull_deposit_without_controller_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let depositor = Address::generate(&env);
+    token_admin.mint(&depositor, &500);
+    token.approve(&depositor, &setup.client.address, &500, &1_000);
+
+    env.set_auths(&[]);
+    assert!(setup
+        .client
+        .try_pull_deposit(&depositor, &token.address, &300, &Memo::None)
+        .is_err());
+}
+
+#[test]
+fn test_pull_deposit_above_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    let depositor = Address::generate(&env);
+    token_admin.mint(&depositor, &500);
+    token.approve(&depositor, &setup.client.address, &100, &1_000);
+
+    assert!(setup
+        .client
+        .try_pull_deposit(&depositor, &token.address, &300, &Memo::None)
+        .is_err());
+    assert_eq!(setup.client.get_balance(&token.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "No untracked deposit to credit")]
+fn test_credit_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 100);
+    let depositor = Address::generate(&env);
+    token_admin.mint(&depositor, &250);
+    token.transfer(&depositor, &setup.client.address, &250);
+
+    assert_eq!(setup.client.credit_deposit(&token.address), 250);
+    assert_eq!(setup.client.get_balance(&token.address), 350);
+    let record = setup.client.get_transactions(&0, &1).get(0).unwrap();
+    assert_eq!(record.amount, 250);
+    assert_eq!(record.counterparty, setup.client.address);
+
+    setup.client.credit_deposit(&token.address);
+}
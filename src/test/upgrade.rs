@@ -0,0 +1,361 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, env_with_long_ttl,
+    passkey, sign_pkey, try_set_allowance, PLATFORM, PRIMARY, PROFILE_ID, WRONG,
+};
+use crate::{
+    data::{LegacyKey, Memo, UPGRADE_TIMELOCK},
+    types::Platform,
+    SubAccountClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
+};
+
+//Release build of the sub-account committed under wasm/
+const SUBACCOUNT_WASM: &[u8] = include_bytes!("../../wasm/socketfi_smart_subaccount.wasm");
+
+//Release build of the sub-account before weighted signers and linked profiles
+const LEGACY_SUBACCOUNT_WASM: &[u8] =
+    include_bytes!("../../wasm/socketfi_smart_subaccount_legacy.wasm");
+
+//Running the upgraded wasm costs far more than the default test budget allows
+fn upload_subaccount_wasm(env: &Env) -> BytesN<32> {
+    env.budget().reset_unlimited();
+    env.deployer().upload_contract_wasm(SUBACCOUNT_WASM)
+}
+
+//Deploys the legacy sub-account, it is driven through its own entry points
+fn register_legacy_account(env: &Env) -> Address {
+    env.budget().reset_unlimited();
+    env.register_contract_wasm(None, LEGACY_SUBACCOUNT_WASM)
+}
+
+fn invoke_legacy(env: &Env, contract_id: &Address, function: &str, args: Vec<Val>) {
+    env.invoke_contract::<()>(contract_id, &Symbol::new(env, function), args);
+}
+
+//The legacy upgrade is authorized by the owner alone and takes no approved hash list
+fn upgrade_legacy_account<'a>(env: &Env, contract_id: &Address) -> SubAccountClient<'a> {
+    let wasm_hash = upload_subaccount_wasm(env);
+    invoke_legacy(
+        env,
+        contract_id,
+        "upgrade",
+        vec![env, wasm_hash.into_val(env)],
+    );
+    SubAccountClient::new(env, contract_id)
+}
+
+#[test]
+fn test_set_wasm_approved() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+
+    assert!(!setup.client.is_wasm_approved(&wasm_hash));
+    setup.client.set_wasm_approved(&wasm_hash, &true);
+    assert!(setup.client.is_wasm_approved(&wasm_hash));
+    setup.client.set_wasm_approved(&wasm_hash, &false);
+    assert!(!setup.client.is_wasm_approved(&wasm_hash));
+}
+
+#[test]
+#[should_panic]
+fn test_set_wasm_approved_without_controller_auth() {
+    let env = Env::default();
+    let setup = create_account_with_address(&env);
+
+    setup
+        .client
+        .set_wasm_approved(&BytesN::from_array(&env, &[1; 32]), &true);
+}
+
+#[test]
+#[should_panic(expected = "Wasm hash has not been approved by the controller")]
+fn test_upgrade_unapproved_wasm() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+
+    setup
+        .client
+        .upgrade(&setup.signers, &BytesN::from_array(&env, &[1; 32]));
+}
+
+#[test]
+#[should_panic(expected = "Wasm hash has not been approved by the controller")]
+fn test_upgrade_pkey_unapproved_wasm() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "upgrade_pkey",
+        wasm_hash.clone(),
+    );
+    setup.client.upgrade_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &wasm_hash,
+    );
+}
+
+#[test]
+fn test_upgrade_wrong_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &WRONG,
+        "upgrade_pkey",
+        wasm_hash.clone(),
+    );
+    assert!(!setup.client.upgrade_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &wasm_hash,
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Owner not set")]
+fn test_upgrade_without_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    setup
+        .client
+        .upgrade(&Vec::new(&env), &BytesN::from_array(&env, &[1; 32]));
+}
+
+#[test]
+fn test_propose_and_cancel_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    setup.client.set_wasm_approved(&wasm_hash, &true);
+
+    setup.client.propose_upgrade(&wasm_hash);
+    let pending = setup.client.get_pending_upgrade().unwrap();
+    assert_eq!(pending.wasm_hash, wasm_hash);
+    assert_eq!(
+        pending.unlock_ledger,
+        env.ledger().sequence() + UPGRADE_TIMELOCK
+    );
+
+    let auth = sign_pkey(&env, &setup.client, &PRIMARY, "cancel_upgrade_pkey", ());
+    setup
+        .client
+        .cancel_upgrade_pkey(&1, &auth.nonce, &auth.expiration_ledger, &auth.proof);
+    assert!(setup.client.get_pending_upgrade().is_none());
+}
+
+#[test]
+#[should_panic(expected = "Upgrade timelock has not expired")]
+fn test_execute_upgrade_before_timelock() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    setup.client.set_wasm_approved(&wasm_hash, &true);
+
+    setup.client.propose_upgrade(&wasm_hash);
+    env.ledger()
+        .with_mut(|li| li.sequence_number += UPGRADE_TIMELOCK - 1);
+    setup.client.execute_upgrade();
+}
+
+#[test]
+#[should_panic(expected = "No pending upgrade")]
+fn test_execute_cancelled_upgrade() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    setup.client.set_wasm_approved(&wasm_hash, &true);
+
+    setup.client.propose_upgrade(&wasm_hash);
+    setup.client.cancel_upgrade_addr(&setup.signers);
+    env.ledger()
+        .with_mut(|li| li.sequence_number += UPGRADE_TIMELOCK);
+    setup.client.execute_upgrade();
+}
+
+#[test]
+fn test_upgrade_pkey() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let wasm_hash = upload_subaccount_wasm(&env);
+    setup.client.set_wasm_approved(&wasm_hash, &true);
+
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "upgrade_pkey",
+        wasm_hash.clone(),
+    );
+    setup.client.upgrade_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &wasm_hash,
+    );
+    assert_eq!(setup.client.get_allowance(), super::MAX_ALLOWANCE);
+}
+
+#[test]
+fn test_execute_upgrade_after_timelock() {
+    let env = env_with_long_ttl();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let wasm_hash = upload_subaccount_wasm(&env);
+    setup.client.set_wasm_approved(&wasm_hash, &true);
+
+    setup.client.propose_upgrade(&wasm_hash);
+    env.ledger()
+        .with_mut(|li| li.sequence_number += UPGRADE_TIMELOCK);
+    setup.client.execute_upgrade();
+
+    assert!(setup.client.get_pending_upgrade().is_none());
+    assert_eq!(setup.client.get_signer_weight(&setup.owner), 1);
+}
+
+#[test]
+fn test_upgrade_legacy_owner_account() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = register_legacy_account(&env);
+    let controller = Address::generate(&env);
+    let owner = Address::generate(&env);
+    invoke_legacy(
+        &env,
+        &contract_id,
+        "init_with_address",
+        vec![&env, controller.into_val(&env), owner.into_val(&env)],
+    );
+    let (token, token_admin) = create_token(&env);
+    let depositor = Address::generate(&env);
+    token_admin.mint(&depositor, &500);
+    invoke_legacy(
+        &env,
+        &contract_id,
+        "receive",
+        vec![
+            &env,
+            depositor.into_val(&env),
+            token.address.into_val(&env),
+            300_i128.into_val(&env),
+        ],
+    );
+
+    let client = upgrade_legacy_account(&env, &contract_id);
+    assert_eq!(client.get_owner(), owner);
+    assert_eq!(client.get_signer_weight(&owner), 1);
+    assert_eq!(client.get_creation_platform(), Platform::Stellar);
+    assert_eq!(client.get_balance(&token.address), 300);
+
+    let to = Address::generate(&env);
+    client.send_auth_addr(&vec![&env, owner], &to, &token.address, &100, &Memo::None);
+    assert_eq!(token.balance(&to), 100);
+    assert_eq!(client.get_balance(&token.address), 200);
+}
+
+#[test]
+fn test_migrate_legacy_profile() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = register_legacy_account(&env);
+    let controller = Address::generate(&env);
+    let executor = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let passkey_hash = String::from_str(&env, "passkey_hash");
+    invoke_legacy(
+        &env,
+        &contract_id,
+        "init_with_profile",
+        vec![
+            &env,
+            controller.into_val(&env),
+            String::from_str(&env, "twitter").into_val(&env),
+            String::from_str(&env, PROFILE_ID).into_val(&env),
+            String::from_str(&env, "salt").into_val(&env),
+            String::from_str(&env, "salt_iv").into_val(&env),
+            String::from_str(&env, "key_index").into_val(&env),
+            String::from_str(&env, "index_iv").into_val(&env),
+            passkey_hash.into_val(&env),
+            super::MAX_ALLOWANCE.into_val(&env),
+        ],
+    );
+    invoke_legacy(
+        &env,
+        &contract_id,
+        "set_executor",
+        vec![&env, 1_u32.into_val(&env), executor.into_val(&env)],
+    );
+    invoke_legacy(&env, &contract_id, "set_executor_done", vec![&env]);
+    invoke_legacy(
+        &env,
+        &contract_id,
+        "set_owner_pkey",
+        vec![
+            &env,
+            1_u32.into_val(&env),
+            passkey_hash.into_val(&env),
+            owner.into_val(&env),
+        ],
+    );
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&LegacyKey::Nonce, &3_u32);
+    });
+
+    let client = upgrade_legacy_account(&env, &contract_id);
+    client.migrate_profile(&PLATFORM, &passkey(&env));
+
+    let profile_id = String::from_str(&env, PROFILE_ID);
+    assert_eq!(client.get_linked_profiles().len(), 1);
+    assert_eq!(client.get_creation_platform(), PLATFORM);
+    assert_eq!(client.get_nonce(&PLATFORM, &profile_id), 3);
+    assert_eq!(
+        client
+            .get_encrypted_keys(&PLATFORM, &profile_id)
+            .gen_salt_encrypted,
+        String::from_str(&env, "salt")
+    );
+    assert_eq!(client.get_owner(), owner);
+    assert!(try_set_allowance(&env, &client, &PRIMARY, 500));
+    assert_eq!(client.get_allowance(), 500);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&LegacyKey::ProfileKeys));
+        assert!(!env.storage().instance().has(&LegacyKey::PassKeyHash));
+        assert!(!env.storage().persistent().has(&LegacyKey::Nonce));
+    });
+}
+
+#[test]
+#[should_panic(expected = "No legacy profile to migrate")]
+fn test_migrate_profile_without_legacy_profile() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+
+    setup.client.migrate_profile(&PLATFORM, &passkey(&env));
+}
@@ -0,0 +1,236 @@
+use super::{
+    create_account_with_address, create_account_with_profile, create_token, fund_account,
+    sign_pkey, PRIMARY,
+};
+use crate::data::Memo;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+#[test]
+fn test_deposit_and_withdraw_vault() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let lock_until = env.ledger().sequence() + 100;
+
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "holiday"),
+        &token.address,
+        &1_000,
+        &lock_until,
+    );
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &300);
+
+    //vault funds are reserved, they stay on the account but are no longer spendable
+    assert_eq!(setup.client.get_balance(&token.address), 200);
+    assert_eq!(setup.client.get_tokens().get(0).unwrap().balance, 200);
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 300);
+    assert_eq!(token.balance(&setup.client.address), 500);
+    let vault = setup.client.get_vault(&vault_id).unwrap();
+    assert_eq!(vault.balance, 300);
+    assert_eq!(vault.goal, 1_000);
+
+    env.ledger().with_mut(|li| li.sequence_number = lock_until);
+    setup
+        .client
+        .withdraw_vault_addr(&setup.signers, &vault_id, &100);
+    assert_eq!(setup.client.get_balance(&token.address), 300);
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 200);
+    assert_eq!(setup.client.get_vault(&vault_id).unwrap().balance, 200);
+}
+
+#[test]
+#[should_panic(expected = "Vault is locked")]
+fn test_withdraw_locked_vault() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "holiday"),
+        &token.address,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &300);
+
+    setup
+        .client
+        .withdraw_vault_addr(&setup.signers, &vault_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "Amount exceeds the unreserved balance")]
+fn test_pkey_send_cannot_spend_vault() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_profile(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+
+    let name = String::from_str(&env, "rent");
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "create_vault_pkey",
+        (name.clone(), token.address.clone(), 0_i128, 0_u32),
+    );
+    let vault_id = setup.client.create_vault_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &name,
+        &token.address,
+        &0,
+        &0,
+    );
+    assert_eq!(vault_id, Some(1));
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "deposit_vault_pkey",
+        (1_u32, 400_i128),
+    );
+    assert!(setup.client.deposit_vault_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &1,
+        &400,
+    ));
+    assert_eq!(setup.client.get_reserved_balance(&token.address), 400);
+
+    let recipient = Address::generate(&env);
+    let auth = sign_pkey(
+        &env,
+        &setup.client,
+        &PRIMARY,
+        "send_with_pkey",
+        (
+            recipient.clone(),
+            token.address.clone(),
+            200_i128,
+            Memo::None,
+        ),
+    );
+    setup.client.send_with_pkey(
+        &1,
+        &auth.nonce,
+        &auth.expiration_ledger,
+        &auth.proof,
+        &recipient,
+        &token.address,
+        &200,
+        &Memo::None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Not enough unreserved balance for the vault")]
+fn test_deposit_above_unreserved_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "holiday"),
+        &token.address,
+        &0,
+        &0,
+    );
+
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &501);
+}
+
+#[test]
+#[should_panic(expected = "Amount exceeds the vault balance")]
+fn test_withdraw_above_vault_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "holiday"),
+        &token.address,
+        &0,
+        &0,
+    );
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &100);
+
+    setup
+        .client
+        .withdraw_vault_addr(&setup.signers, &vault_id, &101);
+}
+
+#[test]
+#[should_panic(expected = "Not enough unreserved balance for the vault")]
+fn test_vault_cannot_reserve_approved_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "holiday"),
+        &token.address,
+        &0,
+        &0,
+    );
+    setup.client.approve_spender_addr(
+        &setup.signers,
+        &token.address,
+        &Address::generate(&env),
+        &300,
+        &1_000,
+    );
+
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &201);
+}
+
+#[test]
+#[should_panic(expected = "No untracked deposit to credit")]
+fn test_vault_deposit_is_not_credited_again() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = create_account_with_address(&env);
+    let (token, token_admin) = create_token(&env);
+    fund_account(&env, &setup.client, &token_admin, 500);
+    let vault_id = setup.client.create_vault_addr(
+        &setup.signers,
+        &String::from_str(&env, "holiday"),
+        &token.address,
+        &0,
+        &0,
+    );
+    setup
+        .client
+        .deposit_vault_addr(&setup.signers, &vault_id, &300);
+
+    setup.client.credit_deposit(&token.address);
+}
@@ -1,11 +1,53 @@
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{token, Address, Bytes, Env, Vec};
 
 // use crate::rates::read_sale_rate;
 use crate::{
-    balance::read_balance,
-    data::{DataKey, Token, BUMP_AMOUNT, LIFETIME_THRESHOLD},
+    balance::{read_current_balance, read_spendable},
+    data::{DataKey, Token, TokenMetadata, BUMP_AMOUNT, LIFETIME_THRESHOLD},
 };
 
+//XDR of Asset::Native, the native asset contract address is derived from it
+const NATIVE_ASSET_XDR: [u8; 4] = [0, 0, 0, 0];
+
+//Address of the Stellar Asset Contract of XLM on the current network
+pub fn native_token(e: &Env) -> Address {
+    e.deployer()
+        .with_stellar_asset(Bytes::from_array(e, &NATIVE_ASSET_XDR))
+        .deployed_address()
+}
+
+//Metadata is cached for registered tokens only, the metadata of other tokens and of tokens
+//registered before it was cached is fetched from the token contract without being stored
+pub fn read_token_metadata(e: &Env, token_id: Address) -> TokenMetadata {
+    let key = DataKey::TokenMetadata(token_id.clone());
+    if let Some(metadata) = e.storage().persistent().get::<DataKey, TokenMetadata>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        return metadata;
+    }
+    fetch_token_metadata(e, &token_id)
+}
+
+fn fetch_token_metadata(e: &Env, token_id: &Address) -> TokenMetadata {
+    let token = token::Client::new(e, token_id);
+    TokenMetadata {
+        decimals: token.decimals(),
+        symbol: token.symbol(),
+        name: token.name(),
+    }
+}
+
+//Persistent so the symbol and name strings of the token contract do not grow the instance entry
+fn write_token_metadata(e: &Env, token_id: Address) {
+    let metadata = fetch_token_metadata(e, &token_id);
+    let key = DataKey::TokenMetadata(token_id);
+    e.storage().persistent().set(&key, &metadata);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
 pub fn read_has_been_added(e: &Env, token_id: Address) -> bool {
     let key = DataKey::TokenAdded(token_id);
     e.storage().instance().get(&key).unwrap_or(false)
@@ -41,20 +83,33 @@ pub fn write_token_count(e: &Env, new_count: u32) {
         .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
 }
 
-pub fn read_tokens(e: &Env) -> Vec<Token> {
-    let mut tokens: Vec<Token> = Vec::new(&e);
-    let token_count = read_token_count(e);
-    for index in 1..=token_count {
+//Every token registered by the account
+pub fn read_token_ids(e: &Env) -> Vec<Address> {
+    let mut token_ids = Vec::new(e);
+    for index in 1..=read_token_count(e) {
         let key = DataKey::TokenIds(index);
-        let token: Address = e.storage().instance().get(&key).unwrap();
-        let balance = read_balance(e, token.clone());
+        token_ids.push_back(e.storage().instance().get(&key).unwrap());
+    }
+    token_ids
+}
+
+pub fn read_tokens(e: &Env) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new(e);
+    let native = native_token(e);
+    for token in read_token_ids(e).iter() {
+        let balance = read_current_balance(e, token.clone());
         let smart_transact_status = read_smart_transact_active(e, token.clone());
 
         if balance > 0 {
+            let metadata = read_token_metadata(e, token.clone());
             let token_info = Token {
+                is_native: token == native,
+                balance: read_spendable(e, token.clone()),
                 token_id: token,
-                balance: balance,
                 smart_transact: smart_transact_status,
+                decimals: metadata.decimals,
+                symbol: metadata.symbol,
+                name: metadata.name,
             };
 
             tokens.push_back(token_info);
@@ -67,3 +122,17 @@ pub fn save_token_id(e: &Env, index: u32, token_id: Address) {
     let key = DataKey::TokenIds(index);
     e.storage().instance().set(&key, &token_id);
 }
+
+//Registers a token the first time the account holds it so it is listed by read_tokens
+//and caches its decimals, symbol and name
+pub fn add_token(e: &Env, token_id: Address) {
+    let has_been_added = read_has_been_added(e, token_id.clone());
+    if !has_been_added {
+        let key = DataKey::TokenAdded(token_id.clone());
+        e.storage().instance().set(&key, &true);
+        write_token_metadata(e, token_id.clone());
+        let new_count = read_token_count(e) + 1;
+        save_token_id(e, new_count, token_id);
+        write_token_count(e, new_count)
+    }
+}
@@ -1,8 +1,17 @@
-use crate::data::{DataKey, BUMP_AMOUNT, LIFETIME_THRESHOLD};
-use soroban_sdk::{token, Address, Bytes, Env};
+use crate::{
+    balance::{read_accounted, read_available, read_token_balance, write_accounted},
+    data::{
+        AuthPath, DataKey, Memo, TransactionDirection, TransactionRecord, BUMP_AMOUNT,
+        LIFETIME_THRESHOLD, MAX_MEMO_LENGTH, MAX_TRANSACTION_HISTORY,
+    },
+    events::transaction_recorded,
+    types::Platform,
+};
+use soroban_sdk::{token, Address, Bytes, Env, String, Vec};
 
-pub fn read_nonce(e: &Env) -> u32 {
-    let key = DataKey::Nonce;
+//Nonce of a linked profile, every passkey authorized call must use the current value
+pub fn read_nonce(e: &Env, platform: Platform, profile_id: String) -> u32 {
+    let key = DataKey::Nonce(platform, profile_id);
     if let Some(nonce) = e.storage().persistent().get::<DataKey, u32>(&key) {
         e.storage()
             .persistent()
@@ -13,22 +22,26 @@ pub fn read_nonce(e: &Env) -> u32 {
     }
 }
 
-pub fn increase_nonce(e: &Env, inc: u32) {
-    let key = DataKey::Nonce;
-    let new_nonce = read_nonce(e) + inc;
-    e.storage().persistent().set(&key, &new_nonce);
+pub fn write_nonce(e: &Env, platform: Platform, profile_id: String, nonce: u32) {
+    let key = DataKey::Nonce(platform, profile_id);
+    e.storage().persistent().set(&key, &nonce);
     e.storage()
         .persistent()
         .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
 }
 
+pub fn increase_nonce(e: &Env, platform: Platform, profile_id: String) {
+    let new_nonce = read_nonce(e, platform, profile_id.clone()) + 1;
+    write_nonce(e, platform, profile_id, new_nonce);
+}
+
 pub fn read_tx_nonce(e: &Env) -> Bytes {
     let key = DataKey::TxNonce;
 
     if let Some(nonce) = e.storage().instance().get(&key) {
         nonce
     } else {
-        Bytes::new(&e)
+        Bytes::new(e)
     }
 }
 
@@ -49,27 +62,181 @@ pub fn read_transact_no(e: &Env) -> u32 {
     }
 }
 
-pub fn inc_transact_no(e: &Env) {
+pub fn inc_transact_no(e: &Env) -> u32 {
     let key = DataKey::TransactionCount;
     let new_count = read_transact_no(e) + 1;
     e.storage().persistent().set(&key, &new_count);
     e.storage()
         .persistent()
         .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+    new_count
 }
 
-pub fn take_token(env: &Env, from: &Address, token_id: &Address, amount: i128) {
+pub fn check_memo(memo: &Memo) {
+    if let Memo::Text(text) = memo {
+        if text.len() > MAX_MEMO_LENGTH {
+            panic!("Memo is too long")
+        }
+    }
+}
+
+//Only the last MAX_TRANSACTION_HISTORY records are kept, a new record overwrites the oldest one
+fn write_transaction(
+    e: &Env,
+    direction: TransactionDirection,
+    token_id: &Address,
+    counterparty: &Address,
+    amount: i128,
+    auth: AuthPath,
+    memo: Memo,
+) {
+    let id = inc_transact_no(e);
+    let record = TransactionRecord {
+        id,
+        direction,
+        token_id: token_id.clone(),
+        counterparty: counterparty.clone(),
+        amount,
+        ledger: e.ledger().sequence(),
+        auth,
+        memo: memo.clone(),
+    };
+    let key = DataKey::Transaction(id % MAX_TRANSACTION_HISTORY);
+    e.storage().persistent().set(&key, &record);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+    transaction_recorded(
+        e,
+        direction,
+        counterparty.clone(),
+        id,
+        token_id.clone(),
+        amount,
+        memo,
+    );
+}
+
+//Newest first, offset skips the most recent transactions
+pub fn read_transactions(e: &Env, offset: u32, limit: u32) -> Vec<TransactionRecord> {
+    let mut records = Vec::new(e);
+    let count = read_transact_no(e);
+    let kept = count.min(MAX_TRANSACTION_HISTORY);
+    let newest = count.saturating_sub(offset);
+    let oldest = count - kept;
+    let mut id = newest;
+    while id > oldest && records.len() < limit {
+        let key = DataKey::Transaction(id % MAX_TRANSACTION_HISTORY);
+        if let Some(record) = e
+            .storage()
+            .persistent()
+            .get::<DataKey, TransactionRecord>(&key)
+        {
+            records.push_back(record);
+        }
+        id -= 1;
+    }
+    records
+}
+
+pub fn take_token(
+    env: &Env,
+    from: &Address,
+    token_id: &Address,
+    amount: i128,
+    auth: AuthPath,
+    memo: Memo,
+) {
+    check_memo(&memo);
     let token = token::Client::new(env, token_id);
     let contract_address = env.current_contract_address();
 
     token.transfer(from, &contract_address, &amount);
-    inc_transact_no(env);
+    write_accounted(env, token_id.clone(), amount);
+    write_transaction(
+        env,
+        TransactionDirection::Received,
+        token_id,
+        from,
+        amount,
+        auth,
+        memo,
+    );
 }
 
-pub fn send_token(env: &Env, to: &Address, token_id: &Address, amount: i128) {
+pub fn send_token(
+    env: &Env,
+    to: &Address,
+    token_id: &Address,
+    amount: i128,
+    auth: AuthPath,
+    memo: Memo,
+) {
+    check_memo(&memo);
+    if amount > read_available(env, token_id.clone()) {
+        panic!("Amount exceeds the unreserved balance")
+    }
     let token = token::Client::new(env, token_id);
     let contract_address = env.current_contract_address();
 
     token.transfer(&contract_address, to, &amount);
-    inc_transact_no(env);
+    write_accounted(env, token_id.clone(), -amount);
+    write_transaction(
+        env,
+        TransactionDirection::Sent,
+        token_id,
+        to,
+        amount,
+        auth,
+        memo,
+    );
+}
+
+//Pulls funds the depositor approved the sub-account to spend
+pub fn pull_token(
+    env: &Env,
+    from: &Address,
+    token_id: &Address,
+    amount: i128,
+    auth: AuthPath,
+    memo: Memo,
+) {
+    check_memo(&memo);
+    let token = token::Client::new(env, token_id);
+    let contract_address = env.current_contract_address();
+
+    token.transfer_from(&contract_address, from, &contract_address, &amount);
+    write_accounted(env, token_id.clone(), amount);
+    write_transaction(
+        env,
+        TransactionDirection::Received,
+        token_id,
+        from,
+        amount,
+        auth,
+        memo,
+    );
+}
+
+//Funds sent straight to the contract address are the difference between the token balance
+//and the accounted balance, the sender is unknown so the account itself is the counterparty
+//the spender pulls must be recorded first
+pub fn credit_token(env: &Env, token_id: &Address) -> i128 {
+    let contract_address = env.current_contract_address();
+
+    let amount = read_token_balance(env, token_id) - read_accounted(env, token_id.clone());
+    if amount <= 0 {
+        panic!("No untracked deposit to credit")
+    }
+    write_accounted(env, token_id.clone(), amount);
+    write_transaction(
+        env,
+        TransactionDirection::Received,
+        token_id,
+        &contract_address,
+        amount,
+        AuthPath::Depositor,
+        Memo::None,
+    );
+    amount
 }
@@ -1,3 +1 @@
-soroban_sdk::contractimport!(
-    file = "../shared_files/target/wasm32-unknown-unknown/release/socketfi_shared_types.wasm"
-);
+pub use socketfi_shared_types::{Platform, UserPoints};
@@ -0,0 +1,97 @@
+use soroban_sdk::{Address, BytesN, Env};
+
+use crate::{
+    access::write_profile_encrypted,
+    data::{DataKey, LegacyKey, LegacyProfile, PendingUpgrade, ProfileEncrypted},
+    platform::{has_creation_platform, write_creation_platform},
+    signers::write_owner,
+    transact::write_nonce,
+    types::Platform,
+};
+
+//Wasm hashes published by the controller as audited, sub-accounts can only upgrade to these
+pub fn read_is_wasm_approved(e: &Env, wasm_hash: BytesN<32>) -> bool {
+    let key = DataKey::ApprovedWasm(wasm_hash);
+    e.storage().instance().get(&key).unwrap_or(false)
+}
+
+pub fn write_wasm_approved(e: &Env, wasm_hash: BytesN<32>, approved: bool) {
+    let key = DataKey::ApprovedWasm(wasm_hash);
+    if approved {
+        e.storage().instance().set(&key, &true);
+    } else {
+        e.storage().instance().remove(&key);
+    }
+}
+
+pub fn read_pending_upgrade(e: &Env) -> Option<PendingUpgrade> {
+    let key = DataKey::PendingUpgrade;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_pending_upgrade(e: &Env, pending: PendingUpgrade) {
+    let key = DataKey::PendingUpgrade;
+    e.storage().instance().set(&key, &pending);
+}
+
+pub fn remove_pending_upgrade(e: &Env) {
+    let key = DataKey::PendingUpgrade;
+    e.storage().instance().remove(&key);
+}
+
+pub fn upgrade_contract(e: &Env, new_wasm_hash: BytesN<32>) {
+    if !read_is_wasm_approved(e, new_wasm_hash.clone()) {
+        panic!("Wasm hash has not been approved by the controller")
+    }
+    remove_pending_upgrade(e);
+    e.deployer().update_current_contract_wasm(new_wasm_hash);
+}
+
+//Accounts deployed before weighted signers stored a single owner address
+//it becomes the only signer, returns false when there is no owner to move
+pub fn migrate_legacy_owner(e: &Env) -> bool {
+    let key = LegacyKey::Owner;
+    let Some(owner) = e.storage().instance().get::<LegacyKey, Address>(&key) else {
+        return false;
+    };
+    e.storage().instance().remove(&key);
+    write_owner(e, &owner);
+    //Accounts created with an address did not record their creation platform
+    if !has_creation_platform(e) && !e.storage().instance().has(&LegacyKey::ProfileKeys) {
+        write_creation_platform(e, Platform::Stellar);
+    }
+    true
+}
+
+//Accounts deployed before linked profiles stored one profile with a passkey hash
+//the hash cannot verify signatures, so the profile is linked again with the passkey public key
+//and the platform string is replaced by the platform the controller maps it to
+pub fn migrate_legacy_profile(e: &Env, platform: Platform, passkey: BytesN<65>) {
+    let key = LegacyKey::ProfileKeys;
+    let legacy: LegacyProfile = e
+        .storage()
+        .instance()
+        .get(&key)
+        .expect("No legacy profile to migrate");
+    let profile_id = legacy.profile_id.clone();
+    let profile = ProfileEncrypted {
+        platform,
+        profile_id: legacy.profile_id,
+        gen_salt_encrypted: legacy.gen_salt_encrypted,
+        salt_encryption_iv: legacy.salt_encryption_iv,
+        index_encrypted: legacy.index_encrypted,
+        index_encryption_iv: legacy.index_encryption_iv,
+    };
+    write_profile_encrypted(e, profile, passkey);
+
+    let nonce_key = LegacyKey::Nonce;
+    if let Some(nonce) = e.storage().persistent().get::<LegacyKey, u32>(&nonce_key) {
+        write_nonce(e, platform, profile_id, nonce);
+        e.storage().persistent().remove(&nonce_key);
+    }
+    if !has_creation_platform(e) {
+        write_creation_platform(e, platform);
+    }
+    e.storage().instance().remove(&key);
+    e.storage().instance().remove(&LegacyKey::PassKeyHash);
+}
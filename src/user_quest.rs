@@ -1,6 +1,7 @@
 use soroban_sdk::Env;
 
-use crate::data::DataKey;
+use crate::data::{DataKey, QuestAction, QuestRule, DAY_IN_LEDGERS};
+use crate::events::quest_points_awarded;
 use crate::types::UserPoints;
 
 pub fn read_quest_data(e: &Env) -> UserPoints {
@@ -13,3 +14,83 @@ pub fn write_quest_data(e: &Env, data: UserPoints) {
 
     e.storage().instance().set(&key, &data);
 }
+
+//Rewards used until the controller configures a rule for the action
+fn default_quest_rule(action: QuestAction) -> QuestRule {
+    match action {
+        QuestAction::Init => QuestRule {
+            points: 2000,
+            repeatable: false,
+            min_amount: 0,
+            cooldown: 0,
+        },
+        QuestAction::SetAllowance | QuestAction::SetSigner => QuestRule {
+            points: 500,
+            repeatable: false,
+            min_amount: 0,
+            cooldown: 0,
+        },
+        QuestAction::Receive | QuestAction::Send => QuestRule {
+            points: 250,
+            repeatable: true,
+            min_amount: 0,
+            cooldown: DAY_IN_LEDGERS,
+        },
+    }
+}
+
+pub fn read_quest_rule(e: &Env, action: QuestAction) -> QuestRule {
+    let key = DataKey::QuestRule(action);
+    e.storage()
+        .instance()
+        .get(&key)
+        .unwrap_or(default_quest_rule(action))
+}
+
+//The init reward is awarded before the controller can set a rule on the account, so it
+//always uses the default rule
+pub fn write_quest_rule(e: &Env, action: QuestAction, rule: QuestRule) {
+    if action == QuestAction::Init {
+        panic!("The init reward cannot be configured")
+    }
+    let key = DataKey::QuestRule(action);
+    e.storage().instance().set(&key, &rule);
+}
+
+fn read_last_award(e: &Env, action: QuestAction) -> Option<u32> {
+    let key = DataKey::QuestLastAward(action);
+    e.storage().instance().get(&key)
+}
+
+fn write_last_award(e: &Env, action: QuestAction, ledger: u32) {
+    let key = DataKey::QuestLastAward(action);
+    e.storage().instance().set(&key, &ledger);
+}
+
+//Records the activity and awards the points of the action rule if it is eligible
+//amount is the transferred amount for receive and send, zero for other actions
+//the additions saturate since awarding points must never make a transfer fail
+pub fn record_quest_action(e: &Env, action: QuestAction, amount: i128) {
+    let mut user_data = read_quest_data(e);
+    match action {
+        QuestAction::Init => {}
+        QuestAction::SetAllowance => user_data.has_set_allowance = true,
+        QuestAction::SetSigner => user_data.has_set_signer = true,
+        QuestAction::Receive => user_data.has_received = true,
+        QuestAction::Send => user_data.has_sent = true,
+    }
+
+    let rule = read_quest_rule(e, action);
+    let ledger = e.ledger().sequence();
+    let eligible = match read_last_award(e, action) {
+        None => true,
+        Some(last) => rule.repeatable && ledger >= last.saturating_add(rule.cooldown),
+    };
+
+    if eligible && rule.points > 0 && amount >= rule.min_amount {
+        user_data.points = user_data.points.saturating_add(rule.points);
+        write_last_award(e, action, ledger);
+        quest_points_awarded(e, action, rule.points, user_data.points);
+    }
+    write_quest_data(e, user_data);
+}
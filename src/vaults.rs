@@ -0,0 +1,122 @@
+use soroban_sdk::{Address, Env, String};
+
+use crate::{
+    balance::{read_available, write_reserved},
+    data::{DataKey, Vault, BUMP_AMOUNT, LIFETIME_THRESHOLD, MAX_VAULT_NAME_LENGTH},
+    events::{vault_created, vault_deposited, vault_withdrawn},
+};
+
+pub fn read_vault_count(e: &Env) -> u32 {
+    let key = DataKey::VaultCount;
+    if let Some(count) = e.storage().persistent().get::<DataKey, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+        count
+    } else {
+        0
+    }
+}
+
+fn write_vault_count(e: &Env, count: u32) {
+    let key = DataKey::VaultCount;
+    e.storage().persistent().set(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn read_vault(e: &Env, vault_id: u32) -> Option<Vault> {
+    let key = DataKey::Vault(vault_id);
+    e.storage().persistent().get(&key)
+}
+
+fn write_vault(e: &Env, vault: &Vault) {
+    let key = DataKey::Vault(vault.id);
+    e.storage().persistent().set(&key, vault);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LIFETIME_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn create_vault(
+    e: &Env,
+    name: String,
+    token_id: Address,
+    goal: i128,
+    lock_until_ledger: u32,
+) -> u32 {
+    if name.len() > MAX_VAULT_NAME_LENGTH {
+        panic!("Vault name is too long")
+    }
+    if goal < 0 {
+        panic!("Vault goal cannot be negative")
+    }
+    let vault_id = read_vault_count(e) + 1;
+    let vault = Vault {
+        id: vault_id,
+        name,
+        token_id: token_id.clone(),
+        balance: 0,
+        goal,
+        lock_until_ledger,
+    };
+    write_vault(e, &vault);
+    write_vault_count(e, vault_id);
+    vault_created(e, vault_id, token_id, goal);
+    vault_id
+}
+
+//Moves funds from the spendable balance into the vault, like streams the vault funds are only
+//reserved and stay part of the tracked balance
+pub fn deposit_vault(e: &Env, vault_id: u32, amount: i128) {
+    let mut vault = read_vault(e, vault_id).expect("Vault not found");
+    if amount <= 0 {
+        panic!("Vault amount must be greater than zero")
+    }
+    if amount > read_available(e, vault.token_id.clone()) {
+        panic!("Not enough unreserved balance for the vault")
+    }
+    write_reserved(e, vault.token_id.clone(), amount);
+    vault.balance += amount;
+    write_vault(e, &vault);
+    vault_deposited(e, vault_id, amount, vault.balance);
+}
+
+//Empties every vault of the token regardless of its lock, returns the released amount
+pub fn release_vaults(e: &Env, token_id: &Address) -> i128 {
+    let mut released = 0;
+    for vault_id in 1..=read_vault_count(e) {
+        let Some(mut vault) = read_vault(e, vault_id) else {
+            continue;
+        };
+        if vault.token_id != *token_id || vault.balance == 0 {
+            continue;
+        }
+        let amount = vault.balance;
+        vault.balance = 0;
+        write_vault(e, &vault);
+        vault_withdrawn(e, vault_id, amount, 0);
+        released += amount;
+    }
+    write_reserved(e, token_id.clone(), -released);
+    released
+}
+
+//Moves funds back to the spendable balance once the lock has passed
+pub fn withdraw_vault(e: &Env, vault_id: u32, amount: i128) {
+    let mut vault = read_vault(e, vault_id).expect("Vault not found");
+    if e.ledger().sequence() < vault.lock_until_ledger {
+        panic!("Vault is locked")
+    }
+    if amount <= 0 {
+        panic!("Vault amount must be greater than zero")
+    }
+    if amount > vault.balance {
+        panic!("Amount exceeds the vault balance")
+    }
+    write_reserved(e, vault.token_id.clone(), -amount);
+    vault.balance -= amount;
+    write_vault(e, &vault);
+    vault_withdrawn(e, vault_id, amount, vault.balance);
+}